@@ -1,4 +1,5 @@
-use utils::AocBufReader;
+use direction::Rotation;
+use utils::{AocBufReader, ModularCounter};
 
 fn main() {
     println!(
@@ -12,82 +13,41 @@ fn main() {
 }
 
 fn part_1(input: AocBufReader) -> usize {
-    let instructions = parse_instructions(input);
-    let mut dial = Dial::new(100, 50);
-    dial.count_zeros_part_1(instructions)
+    count_zeros_part_1(parse_instructions(input))
 }
 
 fn part_2(input: AocBufReader) -> usize {
-    let instructions = parse_instructions(input);
-    let mut dial = Dial::new(100, 50);
-    dial.count_zeros_part_2(instructions)
+    count_zeros_part_2(parse_instructions(input))
 }
 
-#[derive(Debug, PartialEq, Eq)]
-struct Dial {
-    modulus: isize,
-    current: isize,
+fn new_dial() -> ModularCounter<isize> {
+    ModularCounter::new(100, 50).with_mark(0)
 }
 
-impl Dial {
-    fn new(modulus: isize, current: isize) -> Self {
-        Self { modulus, current }
-    }
-
-    fn turn_dial(&mut self, left_or_right: char, n: isize) -> usize {
-        let mut next = (match left_or_right {
-            'L' => self.current - n,
-            'R' => self.current + n,
-            _ => panic!(),
-        } % self.modulus);
-        if next < 0 {
-            next += self.modulus;
-        }
-        let mut clicks_to_zero = match left_or_right {
-            'L' => self.current,
-            'R' => self.modulus - self.current,
-            _ => panic!(),
-        };
-        if clicks_to_zero == 0 {
-            // if we're starting on zero, this zero-crossing was counted
-            // in the previous instruction
-            clicks_to_zero += self.modulus
-        }
-        self.current = next;
-
-        let remaining_clicks = n - clicks_to_zero;
-        if remaining_clicks >= 0 {
-            (remaining_clicks / self.modulus + 1) as usize
-        } else {
-            0
-        }
-    }
-
-    fn count_zeros_part_1(&mut self, instructions: Vec<(char, isize)>) -> usize {
-        let mut result = 0usize;
-        for (left_or_right, n) in instructions {
-            self.turn_dial(left_or_right, n);
-            if self.current == 0 {
-                result += 1;
-            }
-        }
-        result
-    }
+/// How many moves end on zero
+fn count_zeros_part_1(instructions: Vec<(Rotation, isize)>) -> usize {
+    let mut dial = new_dial();
+    instructions
+        .into_iter()
+        .filter(|(rotation, n)| dial.turn(*rotation, *n).position == 0)
+        .count()
+}
 
-    fn count_zeros_part_2(&mut self, instructions: Vec<(char, isize)>) -> usize {
-        instructions
-            .into_iter()
-            .map(|(left_or_right, n)| self.turn_dial(left_or_right, n))
-            .sum()
-    }
+/// How many clicks land on zero
+fn count_zeros_part_2(instructions: Vec<(Rotation, isize)>) -> usize {
+    let mut dial = new_dial();
+    instructions
+        .into_iter()
+        .map(|(rotation, n)| dial.turn(rotation, n).passes as usize)
+        .sum()
 }
 
-fn parse_instructions(input: impl Iterator<Item = String>) -> Vec<(char, isize)> {
+fn parse_instructions(input: impl Iterator<Item = String>) -> Vec<(Rotation, isize)> {
     input
         .map(|line| {
-            let left_or_right = line.chars().next().unwrap();
+            let rotation = Rotation::from_char(line.chars().next().unwrap());
             let clicks: isize = line[1..].parse().unwrap();
-            (left_or_right, clicks)
+            (rotation, clicks)
         })
         .collect()
 }
@@ -99,19 +59,14 @@ mod tests {
     #[test]
     fn test_parse_instructions() {
         let input = ["L68".to_string(), "L30".to_string(), "R48".to_string()];
-        parse_instructions(input.into_iter());
-    }
-
-    #[test]
-    fn test_turn_dial() {
-        let mut dial = Dial::new(100, 5);
-        dial.turn_dial('L', 10);
-        assert_eq!(dial, Dial::new(100, 95));
+        assert_eq!(
+            parse_instructions(input.into_iter())[2],
+            (Rotation::Clockwise, 48)
+        );
     }
 
     #[test]
     fn test_example_part_1() {
-        let mut dial = Dial::new(100, 50);
         let instructions = parse_instructions(
             [
                 "L68".to_string(),
@@ -128,12 +83,11 @@ mod tests {
             .into_iter(),
         );
 
-        assert_eq!(dial.count_zeros_part_1(instructions), 3)
+        assert_eq!(count_zeros_part_1(instructions), 3)
     }
 
     #[test]
     fn test_example_part_2() {
-        let mut dial = Dial::new(100, 50);
         let instructions = parse_instructions(
             [
                 "L68".to_string(),
@@ -150,6 +104,6 @@ mod tests {
             .into_iter(),
         );
 
-        assert_eq!(dial.count_zeros_part_2(instructions), 6)
+        assert_eq!(count_zeros_part_2(instructions), 6)
     }
 }
@@ -1,8 +1,8 @@
-use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
 
-use itertools::Itertools;
-
-use utils::{shortest_path_length, AocBufReader, DijkstraSearchable};
+use utils::{
+    AocBufReader, BitState, DijkstraSearchable, Gf2Error, Gf2Matrix, LinearProgram, Relation,
+};
 
 fn main() {
     println!(
@@ -18,109 +18,43 @@ fn main() {
 fn part_1(iter: impl Iterator<Item = String>) -> usize {
     iter.map(|line| {
         let (target, _, machine) = Machine::from_string(line);
-        let mut targets: HashSet<Vec<char>> = HashSet::new();
-        let target_len = target.len();
-        targets.insert(target);
-
-        let start: Vec<char> = (0..target_len).map(|_| '.').collect();
-        shortest_path_length(machine, start, targets).unwrap()
+        machine.fewest_presses_for(&target).unwrap()
     })
     .sum()
 }
 
-/// For the first button, iterate through its possible values
-/// For each value, iterate through the second button's possible values
-/// and so on...
-fn part_2_inner(machine: &Machine, target: &[usize]) -> Option<usize> {
-    if target.iter().all(|x| *x == 0) {
-        return Some(0);
-    }
-
-    let target_indicators = joltage_to_indicators(target);
-    match machine.combinations_for_indicators.get(&target_indicators) {
-        Some(combinations) => combinations
-            .iter()
-            .filter(|button_set| {
-                let mut to_subtract = new_zeros(machine.n_lights);
-                for button in button_set.iter() {
-                    for joltage_idx in button.iter() {
-                        to_subtract[*joltage_idx] += 1;
-                    }
-                }
-                target
-                    .iter()
-                    .zip(to_subtract.iter())
-                    .all(|(t, minus)| *t >= *minus)
-            })
-            .filter_map(|button_set| {
-                let mut new_target = target.to_owned();
-                for button in button_set.iter() {
-                    for joltage_idx in button.iter() {
-                        new_target[*joltage_idx] -= 1;
-                    }
-                }
-
-                for joltage in new_target.iter_mut() {
-                    *joltage /= 2;
-                }
-
-                part_2_inner(machine, &new_target).map(|value| 2 * value + button_set.len())
-            })
-            .min(),
-        None => None,
-    }
-}
-
 fn part_2(iter: impl Iterator<Item = String>) -> usize {
     iter.map(|line| {
-        let (_, target, machine) = Machine::from_string(line.clone());
-        part_2_inner(&machine, &target).unwrap()
+        let (_, target, machine) = Machine::from_string(line);
+        machine.fewest_presses_for_joltage(&target).unwrap()
     })
     .sum()
 }
 
-type Button = Vec<usize>;
-type ButtonCollection = Vec<Button>;
-
 #[derive(Debug, Clone, PartialEq)]
 struct Machine {
     n_lights: usize,
-    buttons: Vec<Button>,
-    combinations_for_indicators: HashMap<Vec<char>, Vec<ButtonCollection>>,
-}
-
-fn new_zeros(len: usize) -> Vec<usize> {
-    (0..len).map(|_| 0).collect()
-}
-
-fn joltage_to_indicators(joltage: &[usize]) -> Vec<char> {
-    joltage
-        .iter()
-        .map(|x| match *x % 2 == 0 {
-            true => '.',
-            false => '#',
-        })
-        .collect()
+    /// each button as the lights it toggles
+    masks: Vec<BitState>,
 }
 
 impl Machine {
-    fn from_string(s: String) -> (Vec<char>, Vec<usize>, Self) {
+    fn from_string(s: String) -> (BitState, Vec<usize>, Self) {
         let mut split = s.split_whitespace();
-        let indicator_lights: Vec<char> = split
-            .next()
-            .map(|x| x[1..(x.len() - 1)].chars().collect())
-            .unwrap();
+        let indicator_lights = BitState::from_str(split.next().unwrap()).unwrap();
 
-        let mut buttons: Vec<Vec<usize>> = vec![];
+        let n_lights = indicator_lights.len();
+        let mut masks: Vec<BitState> = vec![];
         let mut joltage: Vec<usize> = vec![];
         for string in split {
             match string.chars().next().unwrap() {
-                '(' => buttons.push(
-                    string[1..(string.len() - 1)]
+                '(' => {
+                    let button: Vec<usize> = string[1..(string.len() - 1)]
                         .split(',')
                         .map(|x| x.parse().unwrap())
-                        .collect(),
-                ),
+                        .collect();
+                    masks.push(BitState::from_indices(n_lights, &button));
+                }
                 '{' => {
                     joltage = string[1..(string.len() - 1)]
                         .split(',')
@@ -131,55 +65,52 @@ impl Machine {
             }
         }
 
-        let button_powerset: Vec<ButtonCollection> = (0usize..=(buttons.len()))
-            .flat_map(|n_buttons| buttons.iter().cloned().combinations(n_buttons))
-            .collect();
+        (indicator_lights, joltage, Self { n_lights, masks })
+    }
 
-        let n_lights = indicator_lights.len();
-        let mut combinations_for_indicators: HashMap<Vec<char>, Vec<ButtonCollection>> =
-            HashMap::new();
-        for button_collection in button_powerset.into_iter() {
-            let mut joltage = new_zeros(n_lights);
-            for button in button_collection.iter() {
-                for joltage_idx in button.iter() {
-                    joltage[*joltage_idx] += 1;
-                }
+    /// Pressing a button twice undoes it, so each button is pressed 0 or
+    /// 1 times. That's a linear system over GF(2) with a row per light
+    /// and a column per button, and we want its sparsest solution
+    fn fewest_presses_for(&self, target: &BitState) -> Result<usize, Gf2Error> {
+        let mut toggles = Gf2Matrix::zeros(self.n_lights, self.masks.len());
+        for (button_idx, mask) in self.masks.iter().enumerate() {
+            for light_idx in mask.ones() {
+                toggles.set(light_idx, button_idx, true);
             }
-            let indicators = joltage_to_indicators(&joltage);
-            combinations_for_indicators
-                .entry(indicators)
-                .or_default()
-                .push(button_collection)
         }
 
-        (
-            indicator_lights,
-            joltage,
-            Self {
-                n_lights,
-                buttons,
-                combinations_for_indicators,
-            },
-        )
+        let lit: Vec<bool> = target.iter().collect();
+        toggles
+            .min_weight_solution(&lit)
+            .map(|presses| presses.into_iter().filter(|pressed| *pressed).count())
     }
 
-    fn push_button(&self, lights: &[char], button_idx: usize) -> Vec<char> {
-        let mut result = lights.to_owned();
-        for light_idx in self.buttons[button_idx].iter() {
-            let new_char = match lights[*light_idx] {
-                '.' => '#',
-                '#' => '.',
-                _ => panic!(),
-            };
-            result[*light_idx] = new_char;
+    /// Each press adds one to every counter on the button, so we want
+    /// the fewest nonnegative integer presses with sum over buttons of
+    /// presses * button = target: a small integer program
+    fn fewest_presses_for_joltage(&self, target: &[usize]) -> Option<usize> {
+        let mut program = LinearProgram::minimize(&vec![1; self.masks.len()]);
+        for (counter_idx, joltage) in target.iter().enumerate() {
+            let coeffs: Vec<i64> = self
+                .masks
+                .iter()
+                .map(|mask| i64::from(mask.get(counter_idx)))
+                .collect();
+            program.add_constraint(&coeffs, Relation::Equal, *joltage as i64);
         }
+        program
+            .solve_integer()
+            .optimal()
+            .map(|solution| solution.objective as usize)
+    }
 
-        result
+    fn push_button(&self, lights: &BitState, button_idx: usize) -> BitState {
+        lights.toggled(&self.masks[button_idx])
     }
 }
 
 impl DijkstraSearchable for Machine {
-    type Node = Vec<char>;
+    type Node = BitState;
     type Cost = usize;
 
     fn neighbors(
@@ -187,7 +118,7 @@ impl DijkstraSearchable for Machine {
         previous: &Self::Node,
         previous_cost: Self::Cost,
     ) -> Vec<(Self::Node, Self::Cost)> {
-        (0..(self.buttons.len()))
+        (0..(self.masks.len()))
             .map(|button_idx| (self.push_button(previous, button_idx), previous_cost + 1))
             .collect()
     }
@@ -195,6 +126,10 @@ impl DijkstraSearchable for Machine {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
+
+    use utils::shortest_path_length;
+
     use super::*;
 
     #[test]
@@ -202,27 +137,43 @@ mod tests {
         let (lights, _, machine) =
             Machine::from_string("[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}".to_string());
         let next = machine.push_button(&lights, 1);
-        assert_eq!(next, "..##".chars().collect::<Vec<char>>())
+        assert_eq!(next.to_string(), "[..##]")
     }
 
     #[test]
     fn test_part_1_djikstra() {
         let (target, _, machine) =
             Machine::from_string("[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}".to_string());
-        let mut targets: HashSet<Vec<char>> = HashSet::new();
+        let mut targets: HashSet<BitState> = HashSet::new();
         targets.insert(target);
 
         assert_eq!(
-            shortest_path_length(machine, Vec::from_iter("....".chars()), targets),
+            shortest_path_length(machine, BitState::new(4), targets),
             Some(2)
         )
     }
 
     #[test]
-    fn test_part_2_inner() {
+    fn test_part_1() {
+        assert_eq!(
+            part_1(
+                [
+                    "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}",
+                    "[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}",
+                    "[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}",
+                ]
+                .into_iter()
+                .map(|x| x.to_string())
+            ),
+            7
+        )
+    }
+
+    #[test]
+    fn test_fewest_presses_for_joltage() {
         let (_, target, machine) =
             Machine::from_string("[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}".to_string());
-        assert_eq!(part_2_inner(&machine, &target), Some(10));
+        assert_eq!(machine.fewest_presses_for_joltage(&target), Some(10));
     }
 
     #[test]
@@ -230,7 +181,7 @@ mod tests {
         let (_, target, machine) = Machine::from_string(
             "[####.] (0,2,3,4) (0,1,2,3) (1,4) {19,156,19,19,149}".to_string(),
         );
-        part_2_inner(&machine, &target).unwrap();
+        machine.fewest_presses_for_joltage(&target).unwrap();
     }
 
     #[test]
@@ -1,4 +1,5 @@
 use grid::Grid;
+use polyomino::{pack, Polyomino};
 use utils::AocBufReader;
 
 fn main() {
@@ -18,7 +19,7 @@ fn part_1(iter: impl Iterator<Item = String>) -> usize {
             } else if tree.trivially_possible() {
                 1
             } else {
-                panic!()
+                usize::from(tree.packing(&gifts).is_some())
             }
         })
         .sum()
@@ -81,6 +82,16 @@ impl Tree {
         self.gift_counts.iter().sum::<usize>() <= self.n_3x3_cells()
     }
 
+    /// Actually search for a way to fit every gift under the tree
+    fn packing(&self, gifts: &[Gift]) -> Option<Grid<char>> {
+        let pieces: Vec<(Polyomino, usize)> = gifts
+            .iter()
+            .zip(self.gift_counts.iter())
+            .map(|(gift, n_gifts)| (Polyomino::from_grid(&gift.grid), *n_gifts))
+            .collect();
+        pack(self.n_rows, self.n_cols, &pieces)
+    }
+
     fn from_line(line: String) -> Self {
         let mut plot_and_cts = line.split(": ");
         let plot = plot_and_cts.next().unwrap();
@@ -103,3 +114,54 @@ impl Tree {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(
+            part_1(
+                [
+                    "0:",
+                    "###",
+                    "##.",
+                    "##.",
+                    "",
+                    "1:",
+                    "###",
+                    "##.",
+                    ".##",
+                    "",
+                    "2:",
+                    ".##",
+                    "###",
+                    "##.",
+                    "",
+                    "3:",
+                    "##.",
+                    "###",
+                    "##.",
+                    "",
+                    "4:",
+                    "###",
+                    "#..",
+                    "###",
+                    "",
+                    "5:",
+                    "###",
+                    ".#.",
+                    "###",
+                    "",
+                    "4x4: 0 0 0 0 2 0",
+                    "12x5: 1 0 1 0 2 2",
+                    "12x5: 1 0 1 0 3 2",
+                ]
+                .into_iter()
+                .map(|x| x.to_string())
+            ),
+            2
+        )
+    }
+}
@@ -1,5 +1,7 @@
+use std::ops::RangeInclusive;
+
 use span_1d::Span1D;
-use utils::{factors, AocBufReader};
+use utils::{sum_periodic, sum_repeated_blocks, AocBufReader};
 
 fn main() {
     println!(
@@ -24,59 +26,19 @@ fn main() {
     );
 }
 
-fn part_1(spans: Vec<Span1D<usize>>) -> usize {
-    spans
-        .into_iter()
-        .map(|span| span.iter().filter(|x| is_invalid_part_1(*x)).sum::<usize>())
-        .sum()
+fn as_range(span: &Span1D<usize>) -> RangeInclusive<u64> {
+    span.start as u64..=span.last().unwrap() as u64
 }
 
-fn part_2(spans: Vec<Span1D<usize>>) -> usize {
+fn part_1(spans: Vec<Span1D<usize>>) -> u128 {
     spans
-        .into_iter()
-        .map(|span| span.iter().filter(|x| is_invalid_part_2(*x)).sum::<usize>())
+        .iter()
+        .map(|span| sum_repeated_blocks(as_range(span), 2))
         .sum()
 }
 
-fn is_invalid_part_1(id: usize) -> bool {
-    let s = id.to_string();
-    let n_chars = s.len();
-
-    if n_chars % 2 == 1 {
-        false
-    } else {
-        let midpoint = n_chars / 2;
-        s[..midpoint] == s[midpoint..]
-    }
-}
-
-fn is_repeated_substring(s: &str, len: usize) -> bool {
-    let mut start = 0usize;
-    let mut end = len;
-
-    let to_match = &s[start..end];
-
-    while end <= s.len() {
-        if &s[start..end] != to_match {
-            return false;
-        }
-        start += len;
-        end += len;
-    }
-    true
-}
-
-fn is_invalid_part_2(id: usize) -> bool {
-    let mut divisors = factors(id.to_string().len());
-    divisors.pop(); // don't consider a single repetition invalid
-
-    let s = id.to_string();
-    for divisor in divisors.into_iter() {
-        if is_repeated_substring(&s[..], divisor) {
-            return true;
-        }
-    }
-    false
+fn part_2(spans: Vec<Span1D<usize>>) -> u128 {
+    spans.iter().map(|span| sum_periodic(as_range(span))).sum()
 }
 
 fn parse_span(s: String) -> Span1D<usize> {
@@ -110,16 +72,10 @@ mod tests {
     }
 
     #[test]
-    fn test_is_invalid_part_1() {
-        assert!(is_invalid_part_1(1010));
-        assert!(is_invalid_part_1(1188511885));
-        assert!(!is_invalid_part_1(101));
-    }
-
-    #[test]
-    fn test_is_invalid_part_2() {
-        assert!(is_invalid_part_2(1188511885));
-        assert!(is_invalid_part_2(824824824));
-        assert!(!is_invalid_part_2(101));
+    fn test_example() {
+        let example = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
+        let spans = parse_input(example.split(',').map(|x| x.to_string()));
+        assert_eq!(part_1(spans.clone()), 1227775554);
+        assert_eq!(part_2(spans), 4174379265);
     }
 }
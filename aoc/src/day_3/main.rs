@@ -1,4 +1,4 @@
-use utils::{index_of_max, AocBufReader};
+use utils::{largest_subsequence, AocBufReader};
 
 fn main() {
     println!(
@@ -15,24 +15,20 @@ fn main() {
     );
 }
 
-fn _joltage(bank: &[usize], n_digits: usize) -> usize {
-    if n_digits == 1 {
-        let (_, max) = index_of_max(bank);
-        return *max;
-    }
-
-    let len = bank.len();
-    let (idx, digit) = index_of_max(&bank[..(len - (n_digits - 1))]);
-    10usize.pow((n_digits - 1).try_into().unwrap()) * digit
-        + _joltage(&bank[(idx + 1)..], n_digits - 1)
+/// The largest number made of n_digits of the bank's digits, in order
+fn joltage(bank: &[usize], n_digits: usize) -> usize {
+    largest_subsequence(bank, n_digits)
+        .unwrap()
+        .into_iter()
+        .fold(0, |acc, digit| acc * 10 + digit)
 }
 
 fn part_1(banks: Vec<Vec<usize>>) -> usize {
-    banks.into_iter().map(|bank| _joltage(&bank[..], 2)).sum()
+    banks.into_iter().map(|bank| joltage(&bank[..], 2)).sum()
 }
 
 fn part_2(banks: Vec<Vec<usize>>) -> usize {
-    banks.into_iter().map(|bank| _joltage(&bank[..], 12)).sum()
+    banks.into_iter().map(|bank| joltage(&bank[..], 12)).sum()
 }
 
 fn parse_banks(iter: impl Iterator<Item = String>) -> Vec<Vec<usize>> {
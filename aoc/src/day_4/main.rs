@@ -1,7 +1,7 @@
 use std::collections::HashSet;
 
 use coord_2d::Coord2D;
-use grid::Grid;
+use grid::{Grid, PrefixSum2D};
 use utils::AocBufReader;
 
 fn main() {
@@ -26,10 +26,15 @@ fn count_neighbors(x: &Coord2D<usize>, all: &HashSet<Coord2D<usize>>) -> usize {
 
 fn part_1(reader: impl Iterator<Item = String>) -> usize {
     let grid: Grid<char> = Grid::from_line_iter(reader);
-    let paper_rolls = grid.find('@');
-    paper_rolls
+    let rolls_in = PrefixSum2D::new(&grid, |c| usize::from(c == '@'));
+    grid.find('@')
         .iter()
-        .filter(|x| count_neighbors(x, &paper_rolls) < 4)
+        .filter(|x| {
+            // the 3x3 box around x, minus x itself
+            let top_left = Coord2D::new(x.row.saturating_sub(1), x.col.saturating_sub(1));
+            let bottom_right = Coord2D::new(x.row + 1, x.col + 1);
+            rolls_in.sum(&top_left, &bottom_right) - 1 < 4
+        })
         .count()
 }
 
@@ -1,4 +1,4 @@
-use span_1d::Span1D;
+use span_1d::{Span1D, SpanSet};
 use utils::AocBufReader;
 
 fn main() {
@@ -8,14 +8,13 @@ fn main() {
 }
 
 fn part_1(spans: Vec<Span1D<usize>>, ids: Vec<usize>) -> usize {
-    ids.into_iter()
-        .filter(|id| spans.iter().any(|span| span.contains(*id)))
-        .count()
+    let fresh: SpanSet<usize> = spans.into_iter().collect();
+    ids.into_iter().filter(|id| fresh.contains(*id)).count()
 }
 
 fn part_2(spans: Vec<Span1D<usize>>) -> usize {
-    let merged_spans = Span1D::melt(spans);
-    merged_spans.into_iter().map(|span| span.len).sum()
+    let fresh: SpanSet<usize> = spans.into_iter().collect();
+    fresh.covered_len()
 }
 
 fn parse_input(mut iter: impl Iterator<Item = String>) -> (Vec<Span1D<usize>>, Vec<usize>) {
@@ -71,4 +70,16 @@ mod tests {
         );
         assert_eq!(part_1(spans, ids), 3);
     }
+
+    #[test]
+    fn test_part_2() {
+        let (spans, _) = parse_input(
+            [
+                "3-5", "10-14", "16-20", "12-18", "", "1", "5", "8", "11", "17", "32",
+            ]
+            .into_iter()
+            .map(|x| x.to_string()),
+        );
+        assert_eq!(part_2(spans), 14);
+    }
 }
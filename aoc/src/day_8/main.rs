@@ -2,7 +2,8 @@ use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 
 use coord_3d::Coord3D;
-use utils::{pop_set, AocBufReader};
+use kd_tree::KdTree;
+use utils::{pop_set, AocBufReader, UnionFind};
 
 fn main() {
     println!(
@@ -25,34 +26,22 @@ fn main() {
 }
 
 fn part_1(coords: Vec<Coord3D<usize>>, n_connections: usize) -> usize {
-    let (christmas_graph, _) = ChristmasGraph::new(coords, n_connections);
+    let christmas_graph = ChristmasGraph::new(coords, n_connections);
     let clique_sizes = christmas_graph.click_sizes();
     clique_sizes[0] * clique_sizes[1] * clique_sizes[2]
 }
 
+/// Connect the closest pairs one at a time until everything is in one
+/// circuit, and report the pair that finished the job
 fn part_2(coords: Vec<Coord3D<usize>>) -> usize {
-    let n_nodes = coords.len();
-
-    // let's do a binary search
-    let mut min_connections: usize = 1;
-    let mut max_connections = n_nodes * (n_nodes - 1) / 2;
-    loop {
-        let n_connections =
-            std::cmp::max((min_connections + max_connections) / 2, min_connections + 1);
-        let (christmas_graph, last_edge) = ChristmasGraph::new(coords.clone(), n_connections);
-
-        if christmas_graph.is_one_clique() {
-            // we have "too many" connections
-            if max_connections == min_connections + 1 {
-                let (left, right) = last_edge;
-                return left.x * right.x;
-            }
-            max_connections = n_connections;
-        } else {
-            // we have multiple cliques; not enough collections
-            min_connections = n_connections;
+    let tree = KdTree::new(coords);
+    let mut circuits = UnionFind::new(tree.points().len());
+    for (idx_1, idx_2, _) in tree.closest_pairs() {
+        if circuits.union(idx_1, idx_2) && circuits.n_sets() == 1 {
+            return tree.points()[idx_1].x * tree.points()[idx_2].x;
         }
     }
+    panic!("never connected everything");
 }
 
 type Node = Coord3D<usize>;
@@ -64,19 +53,13 @@ struct ChristmasGraph {
 }
 
 impl ChristmasGraph {
-    fn new(junction_boxes: Vec<Node>, n_connections: usize) -> (Self, Edge) {
+    fn new(junction_boxes: Vec<Node>, n_connections: usize) -> Self {
         let nodes = junction_boxes.clone().into_iter().collect::<HashSet<_>>();
-        let mut box_pairs: Vec<Edge> = vec![];
-        for idx_1 in 0..(junction_boxes.len() - 1) {
-            for idx_2 in (idx_1 + 1)..junction_boxes.len() {
-                box_pairs.push((junction_boxes[idx_1].clone(), junction_boxes[idx_2].clone()))
-            }
-        }
-        box_pairs.sort_by_key(|(a, b)| a.squared_euclidean_distance(b));
-
-        let mut edges = box_pairs
-            .into_iter()
+        let tree = KdTree::new(junction_boxes);
+        let edges = tree
+            .closest_pairs()
             .take(n_connections)
+            .map(|(idx_1, idx_2, _)| (tree.points()[idx_1].clone(), tree.points()[idx_2].clone()))
             .collect::<Vec<Edge>>();
         let mut nodes_to_edges: HashMap<Node, Vec<Edge>> =
             nodes.iter().cloned().map(|node| (node, vec![])).collect();
@@ -86,13 +69,10 @@ impl ChristmasGraph {
             nodes_to_edges.get_mut(right).unwrap().push(edge.clone());
         }
 
-        (
-            Self {
-                nodes,
-                nodes_to_edges,
-            },
-            edges.pop().unwrap(),
-        )
+        Self {
+            nodes,
+            nodes_to_edges,
+        }
     }
 
     fn neighbors(&self, node: &Node) -> Vec<Node> {
@@ -134,25 +114,6 @@ impl ChristmasGraph {
         result.sort_by(|a, b| b.cmp(a));
         result
     }
-
-    fn is_one_clique(&self) -> bool {
-        let mut all_nodes = self.nodes.clone();
-        let first = pop_set(&mut all_nodes).unwrap();
-
-        let mut to_visit: Vec<Node> = vec![first];
-        let mut clique_nodes: HashSet<Node> = HashSet::new();
-        while let Some(next) = to_visit.pop() {
-            let new_neighbors = self
-                .neighbors(&next)
-                .into_iter()
-                .filter(|neighbor| !clique_nodes.contains(neighbor))
-                .collect::<Vec<_>>();
-            to_visit.extend(new_neighbors);
-            clique_nodes.insert(next);
-        }
-
-        clique_nodes == self.nodes
-    }
 }
 
 #[cfg(test)]
@@ -4,10 +4,13 @@ use std::hash::Hash;
 use std::ops;
 use std::str::FromStr;
 
+use num::iter::range_inclusive;
 use num::traits::Unsigned;
-use num::{Integer, Signed};
+use num::{Integer, Signed, ToPrimitive};
+use thiserror::Error;
 
-use direction::CardinalDirection;
+use direction::{CardinalDirection, HexDirection, OrdinalDirection};
+use utils::{abs_diff, split_components, ComponentsError};
 
 #[derive(Hash, Eq, PartialEq, Debug, Clone)]
 pub struct Coord2D<T: Integer + PartialOrd + Eq + Hash + Copy> {
@@ -76,6 +79,47 @@ impl<T: Integer + PartialOrd + Eq + Copy + Hash> Coord2D<T> {
 
         n_streets + n_avenues
     }
+
+    /// The number of king moves between the two points
+    pub fn chebyshev_distance(&self, other: &Self) -> T {
+        std::cmp::max(abs_diff(self.row, other.row), abs_diff(self.col, other.col))
+    }
+
+    pub fn squared_euclidean_distance(&self, other: &Self) -> T {
+        let n_rows = abs_diff(self.row, other.row);
+        let n_cols = abs_diff(self.col, other.col);
+        n_rows * n_rows + n_cols * n_cols
+    }
+
+    pub fn euclidean_distance(&self, other: &Self) -> f64
+    where
+        T: ToPrimitive,
+    {
+        let n_rows = abs_diff(self.row, other.row).to_f64().unwrap();
+        let n_cols = abs_diff(self.col, other.col).to_f64().unwrap();
+        n_rows.hypot(n_cols)
+    }
+
+    /// Every coordinate in the rectangle in which the two points
+    /// are the corners (inclusive), row by row
+    pub fn rectangle_coords(&self, other: &Self) -> impl Iterator<Item = Self>
+    where
+        T: ToPrimitive,
+    {
+        let (min_row, max_row) = min_max(self.row, other.row);
+        let (min_col, max_col) = min_max(self.col, other.col);
+        range_inclusive(min_row, max_row).flat_map(move |row| {
+            range_inclusive(min_col, max_col).map(move |col| Self::new(row, col))
+        })
+    }
+}
+
+fn min_max<T: Integer + Copy>(a: T, b: T) -> (T, T) {
+    if a > b {
+        (b, a)
+    } else {
+        (a, b)
+    }
 }
 
 impl<T: Integer + PartialOrd + Eq + Copy + Hash> ops::Add<Coord2D<T>> for Coord2D<T> {
@@ -94,6 +138,98 @@ impl<T: Integer + Signed + PartialOrd + Eq + Copy + Hash> ops::Sub<Coord2D<T>> f
     }
 }
 
+impl<T: Integer + Signed + PartialOrd + Eq + Copy + Hash> Coord2D<T> {
+    /// The coordinates on the Bresenham line from self to other,
+    /// both ends included
+    pub fn line_to(&self, other: &Self) -> Vec<Self> {
+        let d_row = abs_diff(self.row, other.row);
+        let d_col = abs_diff(self.col, other.col);
+        let step_row = if self.row < other.row {
+            T::one()
+        } else {
+            -T::one()
+        };
+        let step_col = if self.col < other.col {
+            T::one()
+        } else {
+            -T::one()
+        };
+
+        let mut result: Vec<Self> = vec![self.clone()];
+        let mut current = self.clone();
+        let mut err = d_col - d_row;
+        while &current != other {
+            let err_2 = err + err;
+            if err_2 > -d_row {
+                err = err - d_row;
+                current.col = current.col + step_col;
+            }
+            if err_2 < d_col {
+                err = err + d_col;
+                current.row = current.row + step_row;
+            }
+            result.push(current.clone());
+        }
+
+        result
+    }
+
+    /// Every coordinate within `radius` of self by manhattan
+    /// distance (a diamond), row by row
+    pub fn manhattan_ball(&self, radius: T) -> Vec<Self> {
+        let mut result: Vec<Self> = vec![];
+        let mut d_row = -radius;
+        while d_row <= radius {
+            let width = radius - d_row.abs();
+            let mut d_col = -width;
+            while d_col <= width {
+                result.push(Self::new(self.row + d_row, self.col + d_col));
+                d_col = d_col + T::one();
+            }
+            d_row = d_row + T::one();
+        }
+        result
+    }
+}
+
+/// The unit step in a direction; rows grow to the south
+impl<T: Integer + Signed + PartialOrd + Eq + Copy + Hash> From<CardinalDirection> for Coord2D<T> {
+    fn from(direction: CardinalDirection) -> Self {
+        OrdinalDirection::from(direction).into()
+    }
+}
+
+/// The unit step in a direction; rows grow to the south
+impl<T: Integer + Signed + PartialOrd + Eq + Copy + Hash> From<OrdinalDirection> for Coord2D<T> {
+    fn from(direction: OrdinalDirection) -> Self {
+        let (row, col) = match direction {
+            OrdinalDirection::North => (-T::one(), T::zero()),
+            OrdinalDirection::NorthEast => (-T::one(), T::one()),
+            OrdinalDirection::East => (T::zero(), T::one()),
+            OrdinalDirection::SouthEast => (T::one(), T::one()),
+            OrdinalDirection::South => (T::one(), T::zero()),
+            OrdinalDirection::SouthWest => (T::one(), -T::one()),
+            OrdinalDirection::West => (T::zero(), -T::one()),
+            OrdinalDirection::NorthWest => (-T::one(), -T::one()),
+        };
+        Self::new(row, col)
+    }
+}
+
+/// The unit step in axial hex coordinates, with `r` as the
+/// row and `q` as the column
+impl<T: Integer + Signed + PartialOrd + Eq + Copy + Hash> From<HexDirection> for Coord2D<T> {
+    fn from(direction: HexDirection) -> Self {
+        let unit = |v: i8| match v {
+            1 => T::one(),
+            -1 => -T::one(),
+            _ => T::zero(),
+        };
+        let (q, r) = direction.axial_offset();
+        Self::new(unit(r), unit(q))
+    }
+}
+
 impl<T: Integer + Unsigned + PartialOrd + Eq + Copy + Hash> Coord2D<T> {
     pub fn neighbors(&self) -> Vec<Self> {
         let mut result: Vec<Self> = Vec::new();
@@ -203,20 +339,66 @@ impl<T: Integer + Unsigned + PartialOrd + Eq + Copy + Hash> Coord2D<T> {
             CardinalDirection::West => self.west(),
         }
     }
+
+    pub fn adjacent_ordinal(&self, direction: &OrdinalDirection) -> Option<Self> {
+        match direction {
+            OrdinalDirection::North => self.north(),
+            OrdinalDirection::NorthEast => self.north_east(),
+            OrdinalDirection::East => Some(self.east()),
+            OrdinalDirection::SouthEast => Some(self.south_east()),
+            OrdinalDirection::South => Some(self.south()),
+            OrdinalDirection::SouthWest => self.south_west(),
+            OrdinalDirection::West => self.west(),
+            OrdinalDirection::NorthWest => self.north_west(),
+        }
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ParseCoord2DError {
+    #[error("unbalanced brackets in {0:?}")]
+    UnbalancedBrackets(String),
+    #[error("expected 2 components, found {0}")]
+    WrongComponentCount(usize),
+    #[error("expected label {expected:?}, found {found:?}")]
+    UnexpectedLabel { expected: String, found: String },
+    #[error("invalid component {0:?}")]
+    InvalidComponent(String),
 }
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct ParseCoord2DUsizeError;
+impl From<ComponentsError> for ParseCoord2DError {
+    fn from(err: ComponentsError) -> Self {
+        match err {
+            ComponentsError::UnbalancedBrackets(s) => ParseCoord2DError::UnbalancedBrackets(s),
+            ComponentsError::WrongComponentCount { found, .. } => {
+                ParseCoord2DError::WrongComponentCount(found)
+            }
+            ComponentsError::UnexpectedLabel { expected, found } => {
+                ParseCoord2DError::UnexpectedLabel { expected, found }
+            }
+        }
+    }
+}
 
-impl FromStr for Coord2D<usize> {
-    type Err = ParseCoord2DUsizeError;
+/// Parses `row,col` with optional whitespace and surrounding `(..)` or
+/// `<..>`. Components can instead be labelled `x=col,y=row`, in either
+/// order. Unlabelled inputs ordered (x, y) want a `swap_coords`
+/// afterwards
+impl<T: Integer + PartialOrd + Eq + Copy + Hash + FromStr> FromStr for Coord2D<T> {
+    type Err = ParseCoord2DError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut iter = s.split(",");
-        Ok(Coord2D::new(
-            iter.next().unwrap().parse().unwrap(),
-            iter.next().unwrap().parse().unwrap(),
-        ))
+        let components = split_components(s, &["y", "x"])?;
+        let parsed = components
+            .into_iter()
+            .map(|component| {
+                component
+                    .parse::<T>()
+                    .map_err(|_| ParseCoord2DError::InvalidComponent(component.to_string()))
+            })
+            .collect::<Result<Vec<T>, _>>()?;
+
+        Ok(Coord2D::new(parsed[0], parsed[1]))
     }
 }
 
@@ -240,4 +422,80 @@ mod tests {
         let neighbors: HashSet<Coord2D<usize>> = HashSet::from_iter(x.neighbors());
         assert_eq!(neighbors.len(), 5);
     }
+
+    #[test]
+    fn test_distances() {
+        let a: Coord2D<usize> = Coord2D::new(1, 2);
+        let b: Coord2D<usize> = Coord2D::new(4, 6);
+        assert_eq!(a.manhattan_distance(&b), 7);
+        assert_eq!(a.chebyshev_distance(&b), 4);
+        assert_eq!(b.squared_euclidean_distance(&a), 25);
+        assert_eq!(a.euclidean_distance(&b), 5.0);
+        assert_eq!(a.rectangle_coords(&b).count(), a.rectangle_area(&b));
+    }
+
+    #[test]
+    fn test_line_to() {
+        let start: Coord2D<isize> = Coord2D::new(0, 0);
+        assert_eq!(
+            start.line_to(&Coord2D::new(2, -4)),
+            vec![
+                Coord2D::new(0, 0),
+                Coord2D::new(0, -1),
+                Coord2D::new(1, -2),
+                Coord2D::new(1, -3),
+                Coord2D::new(2, -4),
+            ]
+        );
+        assert_eq!(start.line_to(&start), vec![start.clone()]);
+        assert_eq!(start.line_to(&Coord2D::new(-3, -3)).len(), 4);
+    }
+
+    #[test]
+    fn test_manhattan_ball() {
+        let center: Coord2D<i32> = Coord2D::new(-1, 3);
+        let ball = center.manhattan_ball(2);
+        assert_eq!(ball.len(), 13);
+        assert!(ball.iter().all(|x| x.manhattan_distance(&center) <= 2));
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("3,4".parse(), Ok(Coord2D::<usize>::new(3, 4)));
+        assert_eq!(" ( -3 , 4 ) ".parse(), Ok(Coord2D::<i64>::new(-3, 4)));
+        assert_eq!("x=3, y=-4".parse(), Ok(Coord2D::<i8>::new(-4, 3)));
+        assert_eq!("y=3,x=4".parse(), Ok(Coord2D::<usize>::new(3, 4)));
+        assert_eq!(
+            "3,4,5".parse::<Coord2D<usize>>(),
+            Err(ParseCoord2DError::WrongComponentCount(3))
+        );
+        assert_eq!(
+            "-3,4".parse::<Coord2D<usize>>(),
+            Err(ParseCoord2DError::InvalidComponent("-3".to_string()))
+        );
+        assert_eq!(
+            "(3,4".parse::<Coord2D<usize>>(),
+            Err(ParseCoord2DError::UnbalancedBrackets("(3,4".to_string()))
+        );
+        assert!("y=3,y=4".parse::<Coord2D<usize>>().is_err());
+    }
+
+    #[test]
+    fn test_unit_vectors() {
+        let origin: Coord2D<isize> = Coord2D::new(5, 5);
+        let x: Coord2D<usize> = Coord2D::new(5, 5);
+        for direction in OrdinalDirection::iter() {
+            let step = origin.clone() + Coord2D::from(direction);
+            let adjacent = x.adjacent_ordinal(&direction).unwrap();
+            assert_eq!(
+                (step.row as usize, step.col as usize),
+                (adjacent.row, adjacent.col)
+            );
+        }
+
+        let hex_sum = HexDirection::iter()
+            .map(Coord2D::<isize>::from)
+            .fold(Coord2D::new(0, 0), |acc, step| acc + step);
+        assert_eq!(hex_sum, Coord2D::new(0, 0));
+    }
 }
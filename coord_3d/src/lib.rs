@@ -4,7 +4,10 @@ use std::hash::Hash;
 use std::ops;
 use std::str::FromStr;
 
-use num::{Integer, Signed};
+use num::{Bounded, Integer, Signed, ToPrimitive};
+use thiserror::Error;
+
+use utils::{abs_diff, bounded_step, split_components, ComponentsError};
 
 #[derive(Hash, Eq, PartialEq, Debug, Clone)]
 pub struct Coord3D<T: Integer + PartialOrd + Eq + Hash + Copy> {
@@ -26,6 +29,15 @@ impl<T: Integer + PartialOrd + Eq + Copy + Hash> Coord3D<T> {
         self.x >= T::zero() && self.y >= T::zero() && self.z >= T::zero()
     }
 
+    /// Given &self and another &Coord3D<T>, calculate the
+    /// volume covered by the cuboid in which the two points
+    /// are the corners (inclusive)
+    pub fn cuboid_volume(&self, other: &Self) -> T {
+        (abs_diff(self.x, other.x) + T::one())
+            * (abs_diff(self.y, other.y) + T::one())
+            * (abs_diff(self.z, other.z) + T::one())
+    }
+
     pub fn manhattan_distance(&self, other: &Self) -> T {
         let n_x = {
             if self.x > other.x {
@@ -81,6 +93,59 @@ impl<T: Integer + PartialOrd + Eq + Copy + Hash> Coord3D<T> {
 
         n_x * n_x + n_y * n_y + n_z * n_z
     }
+
+    pub fn euclidean_distance(&self, other: &Self) -> f64
+    where
+        T: ToPrimitive,
+    {
+        let n_x = abs_diff(self.x, other.x).to_f64().unwrap();
+        let n_y = abs_diff(self.y, other.y).to_f64().unwrap();
+        let n_z = abs_diff(self.z, other.z).to_f64().unwrap();
+        (n_x * n_x + n_y * n_y + n_z * n_z).sqrt()
+    }
+
+    /// The number of king moves between the two points
+    pub fn chebyshev_distance(&self, other: &Self) -> T {
+        std::cmp::max(
+            abs_diff(self.x, other.x),
+            std::cmp::max(abs_diff(self.y, other.y), abs_diff(self.z, other.z)),
+        )
+    }
+}
+
+/// Neighbor enumeration works for signed and unsigned T alike;
+/// neighbors that would fall outside of T's range are skipped
+impl<T: Integer + Bounded + PartialOrd + Eq + Copy + Hash> Coord3D<T> {
+    /// The 26 coordinates that differ from self by at most one on every axis
+    pub fn neighbors(&self) -> Vec<Self> {
+        self.offsets(|dx, dy, dz| (dx, dy, dz) != (0, 0, 0))
+    }
+
+    /// The 6 coordinates that differ from self by one on exactly one axis
+    pub fn cardinal_neighbors(&self) -> Vec<Self> {
+        self.offsets(|dx, dy, dz| dx.abs() + dy.abs() + dz.abs() == 1)
+    }
+
+    fn offsets(&self, keep: impl Fn(i8, i8, i8) -> bool) -> Vec<Self> {
+        let mut result: Vec<Self> = Vec::new();
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    if !keep(dx, dy, dz) {
+                        continue;
+                    }
+                    if let (Some(x), Some(y), Some(z)) = (
+                        bounded_step(self.x, dx),
+                        bounded_step(self.y, dy),
+                        bounded_step(self.z, dz),
+                    ) {
+                        result.push(Self::new(x, y, z));
+                    }
+                }
+            }
+        }
+        result
+    }
 }
 
 impl<T: Integer + PartialOrd + Eq + Copy + Hash> ops::Add<Coord3D<T>> for Coord3D<T> {
@@ -99,18 +164,125 @@ impl<T: Integer + Signed + PartialOrd + Eq + Copy + Hash> ops::Sub<Coord3D<T>> f
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct ParseCoord3DUsizeError;
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ParseCoord3DError {
+    #[error("unbalanced brackets in {0:?}")]
+    UnbalancedBrackets(String),
+    #[error("expected 3 components, found {0}")]
+    WrongComponentCount(usize),
+    #[error("expected label {expected:?}, found {found:?}")]
+    UnexpectedLabel { expected: String, found: String },
+    #[error("invalid component {0:?}")]
+    InvalidComponent(String),
+}
 
-impl FromStr for Coord3D<usize> {
-    type Err = ParseCoord3DUsizeError;
+impl From<ComponentsError> for ParseCoord3DError {
+    fn from(err: ComponentsError) -> Self {
+        match err {
+            ComponentsError::UnbalancedBrackets(s) => ParseCoord3DError::UnbalancedBrackets(s),
+            ComponentsError::WrongComponentCount { found, .. } => {
+                ParseCoord3DError::WrongComponentCount(found)
+            }
+            ComponentsError::UnexpectedLabel { expected, found } => {
+                ParseCoord3DError::UnexpectedLabel { expected, found }
+            }
+        }
+    }
+}
+
+/// Parses `a,b,c` with optional whitespace, surrounding `(..)` or `<..>`,
+/// and optional `x=a,y=b,z=c` labels in any order
+impl<T: Integer + PartialOrd + Eq + Copy + Hash + FromStr> FromStr for Coord3D<T> {
+    type Err = ParseCoord3DError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut iter = s.split(",");
-        Ok(Coord3D::new(
-            iter.next().unwrap().parse().unwrap(),
-            iter.next().unwrap().parse().unwrap(),
-            iter.next().unwrap().parse().unwrap(),
-        ))
+        let components = split_components(s, &["x", "y", "z"])?;
+        let parsed = components
+            .into_iter()
+            .map(|component| {
+                component
+                    .parse::<T>()
+                    .map_err(|_| ParseCoord3DError::InvalidComponent(component.to_string()))
+            })
+            .collect::<Result<Vec<T>, _>>()?;
+
+        Ok(Coord3D::new(parsed[0], parsed[1], parsed[2]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distances() {
+        let a: Coord3D<usize> = Coord3D::new(1, 2, 3);
+        let b: Coord3D<usize> = Coord3D::new(3, 5, 9);
+        assert_eq!(a.manhattan_distance(&b), 11);
+        assert_eq!(b.manhattan_distance(&a), 11);
+        assert_eq!(a.squared_euclidean_distance(&b), 49);
+        assert_eq!(a.euclidean_distance(&b), 7.0);
+        assert_eq!(a.chebyshev_distance(&b), 6);
+        assert_eq!(a.manhattan_distance(&a), 0);
+
+        let c: Coord3D<i32> = Coord3D::new(-1, 4, -2);
+        let d: Coord3D<i32> = Coord3D::new(2, 0, -2);
+        assert_eq!(c.manhattan_distance(&d), 7);
+        assert_eq!(c.squared_euclidean_distance(&d), 25);
+        assert_eq!(d.euclidean_distance(&c), 5.0);
+        assert_eq!(c.chebyshev_distance(&d), 4);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let x: Coord3D<usize> = Coord3D::new(1, 1, 1);
+        assert_eq!(x.neighbors().len(), 26);
+        assert_eq!(x.cardinal_neighbors().len(), 6);
+
+        let x: Coord3D<usize> = Coord3D::new(0, 0, 0);
+        assert_eq!(x.neighbors().len(), 7);
+        assert_eq!(x.cardinal_neighbors().len(), 3);
+
+        let x: Coord3D<i64> = Coord3D::new(0, -5, 0);
+        assert_eq!(x.neighbors().len(), 26);
+        assert!(x
+            .cardinal_neighbors()
+            .iter()
+            .all(|n| n.manhattan_distance(&x) == 1));
+    }
+
+    #[test]
+    fn test_cuboid_volume() {
+        let a: Coord3D<usize> = Coord3D::new(1, 2, 3);
+        assert_eq!(a.cuboid_volume(&a), 1);
+        assert_eq!(a.cuboid_volume(&Coord3D::new(0, 4, 3)), 6);
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            "162,817,812".parse(),
+            Ok(Coord3D::<usize>::new(162, 817, 812))
+        );
+        assert_eq!(
+            "<x=-1, y=0, z=2>".parse(),
+            Ok(Coord3D::<i32>::new(-1, 0, 2))
+        );
+        assert_eq!(
+            "<z=2, x=-1, y=0>".parse(),
+            Ok(Coord3D::<i32>::new(-1, 0, 2))
+        );
+        assert_eq!(
+            "1,2".parse::<Coord3D<usize>>(),
+            Err(ParseCoord3DError::WrongComponentCount(2))
+        );
+        assert_eq!(
+            "1,2,3,4".parse::<Coord3D<usize>>(),
+            Err(ParseCoord3DError::WrongComponentCount(4))
+        );
+        assert_eq!(
+            "1,two,3".parse::<Coord3D<usize>>(),
+            Err(ParseCoord3DError::InvalidComponent("two".to_string()))
+        );
     }
 }
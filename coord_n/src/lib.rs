@@ -0,0 +1,210 @@
+use std::cmp::{Eq, PartialOrd};
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops;
+
+use num::{Bounded, Integer, Signed, ToPrimitive};
+
+use coord_2d::Coord2D;
+use coord_3d::Coord3D;
+use utils::bounded_step;
+
+/// A coordinate with D axes, for puzzles in 4 (or more) dimensions
+#[derive(Hash, Eq, PartialEq, Debug, Clone, Copy)]
+pub struct CoordN<T: Integer + PartialOrd + Eq + Hash + Copy, const D: usize> {
+    pub coords: [T; D],
+}
+
+impl<T: Integer + PartialOrd + Eq + Copy + Hash, const D: usize> CoordN<T, D> {
+    pub fn new(coords: [T; D]) -> Self {
+        Self { coords }
+    }
+
+    pub fn origin() -> Self {
+        Self::new([T::zero(); D])
+    }
+
+    pub fn mul_scalar(&self, v: T) -> Self {
+        Self::new(self.coords.map(|c| c * v))
+    }
+
+    pub fn is_nonnegative(&self) -> bool {
+        self.coords.iter().all(|c| *c >= T::zero())
+    }
+
+    fn axis_distances(&self, other: &Self) -> impl Iterator<Item = T> + '_ {
+        self.coords
+            .iter()
+            .zip(other.coords)
+            .map(|(a, b)| if *a > b { *a - b } else { b - *a })
+    }
+
+    pub fn manhattan_distance(&self, other: &Self) -> T {
+        self.axis_distances(other).fold(T::zero(), |acc, d| acc + d)
+    }
+
+    /// The number of king moves between the two points
+    pub fn chebyshev_distance(&self, other: &Self) -> T {
+        self.axis_distances(other)
+            .fold(T::zero(), |acc, d| std::cmp::max(acc, d))
+    }
+
+    pub fn squared_euclidean_distance(&self, other: &Self) -> T {
+        self.axis_distances(other)
+            .fold(T::zero(), |acc, d| acc + d * d)
+    }
+
+    pub fn euclidean_distance(&self, other: &Self) -> f64
+    where
+        T: ToPrimitive,
+    {
+        self.axis_distances(other)
+            .map(|d| d.to_f64().unwrap().powi(2))
+            .sum::<f64>()
+            .sqrt()
+    }
+}
+
+/// Neighbor enumeration works for signed and unsigned T alike;
+/// neighbors that would fall outside of T's range are skipped
+impl<T: Integer + Bounded + PartialOrd + Eq + Copy + Hash, const D: usize> CoordN<T, D> {
+    /// The 3^D - 1 coordinates that differ from self by at most one on every axis
+    pub fn neighbors(&self) -> Vec<Self> {
+        let n_offsets = 3usize.pow(D as u32);
+        (0..n_offsets)
+            .filter(|code| *code != (n_offsets - 1) / 2) // all zero offsets; self
+            .filter_map(|mut code| {
+                let mut coords = self.coords;
+                for c in coords.iter_mut() {
+                    *c = bounded_step(*c, (code % 3) as i8 - 1)?;
+                    code /= 3;
+                }
+                Some(Self::new(coords))
+            })
+            .collect()
+    }
+
+    /// The 2D coordinates that differ from self by one on exactly one axis
+    pub fn cardinal_neighbors(&self) -> Vec<Self> {
+        (0..D)
+            .flat_map(|axis| [(axis, -1), (axis, 1)])
+            .filter_map(|(axis, delta)| {
+                let mut coords = self.coords;
+                coords[axis] = bounded_step(coords[axis], delta)?;
+                Some(Self::new(coords))
+            })
+            .collect()
+    }
+}
+
+impl<T: Integer + PartialOrd + Eq + Copy + Hash, const D: usize> ops::Index<usize>
+    for CoordN<T, D>
+{
+    type Output = T;
+
+    fn index(&self, axis: usize) -> &T {
+        &self.coords[axis]
+    }
+}
+
+impl<T: Integer + PartialOrd + Eq + Copy + Hash, const D: usize> ops::Add<CoordN<T, D>>
+    for CoordN<T, D>
+{
+    type Output = Self;
+
+    fn add(self, _rhs: CoordN<T, D>) -> Self {
+        let mut coords = self.coords;
+        for (c, r) in coords.iter_mut().zip(_rhs.coords) {
+            *c = *c + r;
+        }
+        Self::new(coords)
+    }
+}
+
+impl<T: Integer + Signed + PartialOrd + Eq + Copy + Hash, const D: usize> ops::Sub<CoordN<T, D>>
+    for CoordN<T, D>
+{
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        let mut coords = self.coords;
+        for (c, o) in coords.iter_mut().zip(other.coords) {
+            *c = *c - o;
+        }
+        Self::new(coords)
+    }
+}
+
+/// Axes are ordered (row, col)
+impl<T: Integer + PartialOrd + Eq + Copy + Hash> From<Coord2D<T>> for CoordN<T, 2> {
+    fn from(coord: Coord2D<T>) -> Self {
+        Self::new([coord.row, coord.col])
+    }
+}
+
+impl<T: Integer + PartialOrd + Eq + Copy + Hash> From<CoordN<T, 2>> for Coord2D<T> {
+    fn from(coord: CoordN<T, 2>) -> Self {
+        Coord2D::new(coord[0], coord[1])
+    }
+}
+
+/// Axes are ordered (x, y, z)
+impl<T: Integer + PartialOrd + Eq + Copy + Hash> From<Coord3D<T>> for CoordN<T, 3> {
+    fn from(coord: Coord3D<T>) -> Self {
+        Self::new([coord.x, coord.y, coord.z])
+    }
+}
+
+impl<T: Integer + PartialOrd + Eq + Copy + Hash> From<CoordN<T, 3>> for Coord3D<T> {
+    fn from(coord: CoordN<T, 3>) -> Self {
+        Coord3D::new(coord[0], coord[1], coord[2])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_neighbors() {
+        let x: CoordN<i32, 4> = CoordN::new([0, -3, 7, 1]);
+        let neighbors: HashSet<CoordN<i32, 4>> = HashSet::from_iter(x.neighbors());
+        assert_eq!(neighbors.len(), 80);
+        assert!(!neighbors.contains(&x));
+        assert!(neighbors.iter().all(|n| n.chebyshev_distance(&x) == 1));
+        assert_eq!(x.cardinal_neighbors().len(), 8);
+
+        let x: CoordN<usize, 4> = CoordN::origin();
+        assert_eq!(x.neighbors().len(), 15);
+        assert_eq!(x.cardinal_neighbors().len(), 4);
+    }
+
+    #[test]
+    fn test_distances_and_arithmetic() {
+        let a: CoordN<i64, 4> = CoordN::new([1, -2, 3, 0]);
+        let b: CoordN<i64, 4> = CoordN::new([2, 0, 0, -1]);
+        assert_eq!(a.manhattan_distance(&b), 7);
+        assert_eq!(a.chebyshev_distance(&b), 3);
+        assert_eq!(a.squared_euclidean_distance(&b), 15);
+        assert_eq!(a.euclidean_distance(&b), 15f64.sqrt());
+        assert_eq!(a + b, CoordN::new([3, -2, 3, -1]));
+        assert_eq!(a - b, CoordN::new([-1, -2, 3, 1]));
+        assert_eq!((a - b).mul_scalar(-1) + a, b);
+    }
+
+    #[test]
+    fn test_conversions() {
+        let coord_2d: Coord2D<usize> = Coord2D::new(3, 4);
+        let coord_n: CoordN<usize, 2> = coord_2d.clone().into();
+        assert_eq!(
+            coord_n.squared_euclidean_distance(&CoordN::origin()),
+            coord_2d.squared_euclidean_distance(&Coord2D::new(0, 0))
+        );
+        assert_eq!(Coord2D::from(coord_n), coord_2d);
+
+        let coord_3d: Coord3D<i32> = Coord3D::new(-1, 2, 5);
+        assert_eq!(Coord3D::from(CoordN::from(coord_3d.clone())), coord_3d);
+    }
+}
@@ -1,3 +1,11 @@
+use std::str::FromStr;
+
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error("bad direction {0:?}")]
+pub struct ParseDirectionError(pub String);
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CardinalDirection {
     North,
@@ -7,6 +15,13 @@ pub enum CardinalDirection {
 }
 
 impl CardinalDirection {
+    /// Every direction, clockwise starting from North
+    pub const ALL: [Self; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    pub fn iter() -> impl Iterator<Item = Self> {
+        Self::ALL.into_iter()
+    }
+
     pub fn turn_right(&self) -> Self {
         match self {
             CardinalDirection::North => CardinalDirection::East,
@@ -25,13 +40,320 @@ impl CardinalDirection {
         }
     }
 
+    pub fn turn(&self, rotation: Rotation) -> Self {
+        match rotation {
+            Rotation::Clockwise => self.turn_right(),
+            Rotation::CounterClockwise => self.turn_left(),
+        }
+    }
+
+    pub fn opposite(&self) -> Self {
+        self.turn_right().turn_right()
+    }
+
+    /// Accepts arrows (`^>v<`), compass letters (`NESW`)
+    /// and screen letters (`URDL`), in either case
+    pub fn try_from_char(c: char) -> Option<Self> {
+        match c.to_ascii_uppercase() {
+            '^' | 'N' | 'U' => Some(Self::North),
+            '>' | 'E' | 'R' => Some(Self::East),
+            'V' | 'S' | 'D' => Some(Self::South),
+            '<' | 'W' | 'L' => Some(Self::West),
+            _ => None,
+        }
+    }
+
+    pub fn from_char(c: char) -> Self {
+        match Self::try_from_char(c) {
+            Some(direction) => direction,
+            None => panic!("bad direction char {}", c),
+        }
+    }
+}
+
+impl FromStr for CardinalDirection {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.trim().chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Self::try_from_char(c),
+            _ => None,
+        }
+        .ok_or_else(|| ParseDirectionError(s.to_string()))
+    }
+}
+
+/// The eight compass directions; turning moves by 45°
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OrdinalDirection {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl OrdinalDirection {
+    /// Every direction, clockwise starting from North
+    pub const ALL: [Self; 8] = [
+        Self::North,
+        Self::NorthEast,
+        Self::East,
+        Self::SouthEast,
+        Self::South,
+        Self::SouthWest,
+        Self::West,
+        Self::NorthWest,
+    ];
+
+    pub fn iter() -> impl Iterator<Item = Self> {
+        Self::ALL.into_iter()
+    }
+
+    fn index(&self) -> usize {
+        Self::ALL.iter().position(|d| d == self).unwrap()
+    }
+
+    /// Rotate 45° clockwise
+    pub fn turn_right(&self) -> Self {
+        Self::ALL[(self.index() + 1) % 8]
+    }
+
+    /// Rotate 45° counterclockwise
+    pub fn turn_left(&self) -> Self {
+        Self::ALL[(self.index() + 7) % 8]
+    }
+
+    pub fn opposite(&self) -> Self {
+        Self::ALL[(self.index() + 4) % 8]
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        self.index() % 2 == 1
+    }
+
+    /// Accepts everything `CardinalDirection::try_from_char` does;
+    /// diagonals need two letters, see `FromStr`
+    pub fn try_from_char(c: char) -> Option<Self> {
+        CardinalDirection::try_from_char(c).map(Self::from)
+    }
+
+    pub fn from_char(c: char) -> Self {
+        match Self::try_from_char(c) {
+            Some(direction) => direction,
+            None => panic!("bad direction char {}", c),
+        }
+    }
+}
+
+impl From<CardinalDirection> for OrdinalDirection {
+    fn from(direction: CardinalDirection) -> Self {
+        match direction {
+            CardinalDirection::North => Self::North,
+            CardinalDirection::East => Self::East,
+            CardinalDirection::South => Self::South,
+            CardinalDirection::West => Self::West,
+        }
+    }
+}
+
+impl FromStr for OrdinalDirection {
+    type Err = ParseDirectionError;
+
+    /// A single direction char, or a vertical and a horizontal
+    /// one back to back (`NE`, `ul`, `v<`, ...)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseDirectionError(s.to_string());
+        let directions = s
+            .trim()
+            .chars()
+            .map(CardinalDirection::try_from_char)
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(err)?;
+
+        match directions[..] {
+            [one] => Ok(one.into()),
+            [CardinalDirection::North, CardinalDirection::East] => Ok(Self::NorthEast),
+            [CardinalDirection::South, CardinalDirection::East] => Ok(Self::SouthEast),
+            [CardinalDirection::South, CardinalDirection::West] => Ok(Self::SouthWest),
+            [CardinalDirection::North, CardinalDirection::West] => Ok(Self::NorthWest),
+            _ => Err(err()),
+        }
+    }
+}
+
+/// The six neighbors on a pointy-topped hex grid; turning moves by 60°.
+/// In axial coordinates `q` grows to the east and `r` to the south east
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HexDirection {
+    NorthEast,
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl HexDirection {
+    /// Every direction, clockwise starting from NorthEast
+    pub const ALL: [Self; 6] = [
+        Self::NorthEast,
+        Self::East,
+        Self::SouthEast,
+        Self::SouthWest,
+        Self::West,
+        Self::NorthWest,
+    ];
+
+    pub fn iter() -> impl Iterator<Item = Self> {
+        Self::ALL.into_iter()
+    }
+
+    fn index(&self) -> usize {
+        Self::ALL.iter().position(|d| d == self).unwrap()
+    }
+
+    /// Rotate 60° clockwise
+    pub fn turn_right(&self) -> Self {
+        Self::ALL[(self.index() + 1) % 6]
+    }
+
+    /// Rotate 60° counterclockwise
+    pub fn turn_left(&self) -> Self {
+        Self::ALL[(self.index() + 5) % 6]
+    }
+
+    pub fn opposite(&self) -> Self {
+        Self::ALL[(self.index() + 3) % 6]
+    }
+
+    /// The (q, r) axial offset of one step in this direction
+    pub fn axial_offset(&self) -> (i8, i8) {
+        match self {
+            Self::NorthEast => (1, -1),
+            Self::East => (1, 0),
+            Self::SouthEast => (0, 1),
+            Self::SouthWest => (-1, 1),
+            Self::West => (-1, 0),
+            Self::NorthWest => (0, -1),
+        }
+    }
+}
+
+impl FromStr for HexDirection {
+    type Err = ParseDirectionError;
+
+    /// `e`, `se`, `sw`, `w`, `nw` or `ne` in either case
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_uppercase().as_str() {
+            "NE" => Ok(Self::NorthEast),
+            "E" => Ok(Self::East),
+            "SE" => Ok(Self::SouthEast),
+            "SW" => Ok(Self::SouthWest),
+            "W" => Ok(Self::West),
+            "NW" => Ok(Self::NorthWest),
+            _ => Err(ParseDirectionError(s.to_string())),
+        }
+    }
+}
+
+/// A turn one way or the other, like a dial or a steering wheel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rotation {
+    Clockwise,
+    CounterClockwise,
+}
+
+impl Rotation {
+    pub fn reverse(&self) -> Self {
+        match self {
+            Rotation::Clockwise => Rotation::CounterClockwise,
+            Rotation::CounterClockwise => Rotation::Clockwise,
+        }
+    }
+
+    /// 1 for clockwise, -1 for counterclockwise
+    pub fn sign(&self) -> i8 {
+        match self {
+            Rotation::Clockwise => 1,
+            Rotation::CounterClockwise => -1,
+        }
+    }
+
+    /// `R` for right (clockwise) or `L` for left, in either case
+    pub fn try_from_char(c: char) -> Option<Self> {
+        match c.to_ascii_uppercase() {
+            'R' => Some(Rotation::Clockwise),
+            'L' => Some(Rotation::CounterClockwise),
+            _ => None,
+        }
+    }
+
     pub fn from_char(c: char) -> Self {
-        match c {
-            '^' => Self::North,
-            '>' => Self::East,
-            'v' => Self::South,
-            '<' => Self::West,
-            _ => panic!("bad direction char {}", c),
+        match Self::try_from_char(c) {
+            Some(rotation) => rotation,
+            None => panic!("bad rotation char {}", c),
+        }
+    }
+}
+
+impl FromStr for Rotation {
+    type Err = ParseDirectionError;
+
+    /// `R`, `L`, `CW` or `CCW` in either case
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_uppercase().as_str() {
+            "R" | "CW" => Ok(Rotation::Clockwise),
+            "L" | "CCW" => Ok(Rotation::CounterClockwise),
+            _ => Err(ParseDirectionError(s.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turning() {
+        for direction in OrdinalDirection::iter() {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(
+                (0..4).fold(direction, |d, _| d.turn_right()),
+                direction.opposite()
+            );
         }
+        for direction in HexDirection::iter() {
+            assert_eq!((0..6).fold(direction, |d, _| d.turn_left()), direction);
+            let (q, r) = direction.axial_offset();
+            assert_eq!(direction.opposite().axial_offset(), (-q, -r));
+        }
+        assert_eq!(CardinalDirection::East.opposite(), CardinalDirection::West);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(CardinalDirection::from_char('U'), CardinalDirection::North);
+        assert_eq!(CardinalDirection::from_char('l'), CardinalDirection::West);
+        assert_eq!(
+            "NE".parse::<OrdinalDirection>(),
+            Ok(OrdinalDirection::NorthEast)
+        );
+        assert_eq!("v<".parse(), Ok(OrdinalDirection::SouthWest));
+        assert_eq!("R".parse(), Ok(OrdinalDirection::East));
+        assert!("EN".parse::<OrdinalDirection>().is_err());
+        assert_eq!("sw".parse(), Ok(HexDirection::SouthWest));
+        assert!("n".parse::<HexDirection>().is_err());
+        assert_eq!(Rotation::from_char('r'), Rotation::Clockwise);
+        assert_eq!("ccw".parse(), Ok(Rotation::CounterClockwise));
+        assert!("x".parse::<Rotation>().is_err());
+        assert_eq!(
+            CardinalDirection::North.turn(Rotation::CounterClockwise),
+            CardinalDirection::West
+        );
     }
 }
@@ -2,6 +2,7 @@ use std::cmp::{Eq, PartialOrd};
 use std::collections::HashSet;
 use std::fmt::Display;
 use std::hash::Hash;
+use std::ops::Range;
 
 use itertools::EitherOrBoth::Both;
 use itertools::Itertools;
@@ -9,6 +10,12 @@ use num::Integer;
 
 use coord_2d::Coord2D;
 
+mod prefix_sum;
+mod worksheet;
+
+pub use prefix_sum::PrefixSum2D;
+pub use worksheet::{Block, Operators, ReadNumbers, Worksheet};
+
 #[derive(Clone, Debug)]
 pub struct Grid<T: Copy + Display + PartialEq> {
     inner: Vec<Vec<T>>,
@@ -19,7 +26,7 @@ pub struct Grid<T: Copy + Display + PartialEq> {
 impl<T: Copy + Display + PartialEq> Grid<T> {
     pub fn new(inner: Vec<Vec<T>>) -> Self {
         let n_rows = inner.len();
-        let n_cols = inner[0].len();
+        let n_cols = inner.first().map_or(0, |row| row.len());
 
         Self {
             inner,
@@ -120,6 +127,24 @@ impl<T: Copy + Display + PartialEq> Grid<T> {
         (0..self.n_cols).map(|col_idx| self.col(col_idx))
     }
 
+    /// A copy of the cells in rows x cols, which must be non-empty and
+    /// inside the grid
+    pub fn sub_grid(&self, rows: Range<usize>, cols: Range<usize>) -> Self {
+        if rows.is_empty() || cols.is_empty() || rows.end > self.n_rows || cols.end > self.n_cols {
+            panic!(
+                "Invalid sub grid ({:?}, {:?}); n_rows: {}, n_cols: {}",
+                rows, cols, self.n_rows, self.n_cols
+            );
+        }
+
+        Self::new(
+            self.inner[rows]
+                .iter()
+                .map(|row| row[cols.clone()].to_vec())
+                .collect(),
+        )
+    }
+
     pub fn se_diagonal(&self, row_idx: usize, col_idx: usize) -> Vec<T> {
         if row_idx != 0 && col_idx != 0 {
             panic!(
@@ -0,0 +1,91 @@
+use std::fmt::Display;
+use std::ops::{Add, Sub};
+
+use num::Zero;
+
+use coord_2d::Coord2D;
+
+use crate::Grid;
+
+/// A summed-area table: after an O(n_rows * n_cols) build, the sum of
+/// any rectangle of the grid is four lookups
+#[derive(Clone, Debug)]
+pub struct PrefixSum2D<S> {
+    /// sums[r][c] is the total of every cell above and left of (r, c),
+    /// exclusive, so row 0 and col 0 are all zero
+    sums: Vec<Vec<S>>,
+    pub n_rows: usize,
+    pub n_cols: usize,
+}
+
+impl<S: Copy + Zero + Add<Output = S> + Sub<Output = S>> PrefixSum2D<S> {
+    /// Build from a grid, turning each cell into a number with to_value
+    pub fn new<T: Copy + Display + PartialEq>(grid: &Grid<T>, to_value: impl Fn(T) -> S) -> Self {
+        let mut sums: Vec<Vec<S>> = vec![vec![S::zero(); grid.n_cols + 1]; grid.n_rows + 1];
+        for (row_idx, row) in grid.inner.iter().enumerate() {
+            for (col_idx, val) in row.iter().enumerate() {
+                sums[row_idx + 1][col_idx + 1] =
+                    to_value(*val) + sums[row_idx][col_idx + 1] + sums[row_idx + 1][col_idx]
+                        - sums[row_idx][col_idx];
+            }
+        }
+
+        Self {
+            sums,
+            n_rows: grid.n_rows,
+            n_cols: grid.n_cols,
+        }
+    }
+
+    /// The sum over the rectangle with opposite corners a and b
+    /// (inclusive, in either order). Corners past the bottom or right
+    /// edge are clamped to the grid
+    pub fn sum(&self, a: &Coord2D<usize>, b: &Coord2D<usize>) -> S {
+        if self.n_rows == 0 || self.n_cols == 0 {
+            return S::zero();
+        }
+        let (top, bottom) = (a.row.min(b.row), a.row.max(b.row).min(self.n_rows - 1));
+        let (left, right) = (a.col.min(b.col), a.col.max(b.col).min(self.n_cols - 1));
+        if top > bottom || left > right {
+            return S::zero();
+        }
+
+        self.sums[bottom + 1][right + 1] + self.sums[top][left]
+            - self.sums[top][right + 1]
+            - self.sums[bottom + 1][left]
+    }
+
+    /// The sum over the whole grid
+    pub fn total(&self) -> S {
+        self.sums[self.n_rows][self.n_cols]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sum() {
+        let grid = Grid::from_line_iter(
+            ["#..#", ".##.", "####", "...."]
+                .into_iter()
+                .map(|x| x.to_string()),
+        );
+        let sums = PrefixSum2D::new(&grid, |c| usize::from(c == '#'));
+
+        assert_eq!(sums.total(), 8);
+        assert_eq!(sums.sum(&Coord2D::new(1, 1), &Coord2D::new(2, 2)), 4);
+        assert_eq!(sums.sum(&Coord2D::new(2, 2), &Coord2D::new(0, 0)), 6);
+        assert_eq!(sums.sum(&Coord2D::new(0, 3), &Coord2D::new(0, 3)), 1);
+        assert_eq!(sums.sum(&Coord2D::new(2, 1), &Coord2D::new(10, 10)), 3);
+        assert_eq!(sums.sum(&Coord2D::new(3, 0), &Coord2D::new(3, 3)), 0);
+    }
+
+    #[test]
+    fn test_empty() {
+        let no_cols = PrefixSum2D::new(&Grid::new(vec![vec![]]), |c: char| usize::from(c == '#'));
+        assert_eq!(no_cols.sum(&Coord2D::new(0, 0), &Coord2D::new(5, 5)), 0);
+        assert_eq!(no_cols.total(), 0);
+    }
+}
@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+use std::ops::{Add, Mul};
+use std::str::FromStr;
+
+use num::{One, Zero};
+
+use crate::Grid;
+
+/// How the numbers in a block are laid out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadNumbers {
+    /// One number per row, digits left to right. Reversed starts from
+    /// the bottom row
+    ByRow { reversed: bool },
+    /// One number per column, digits top to bottom. Reversed starts
+    /// from the rightmost column
+    ByCol { reversed: bool },
+}
+
+/// The value of an empty problem, and how to combine two values
+#[derive(Clone, Copy, Debug)]
+struct Operator<T> {
+    identity: T,
+    combine: fn(T, T) -> T,
+}
+
+/// The operators a worksheet can use, by symbol
+#[derive(Clone, Debug)]
+pub struct Operators<T> {
+    table: HashMap<char, Operator<T>>,
+}
+
+impl<T: Copy> Operators<T> {
+    pub fn empty() -> Self {
+        Operators {
+            table: HashMap::new(),
+        }
+    }
+
+    pub fn with(mut self, symbol: char, identity: T, combine: fn(T, T) -> T) -> Self {
+        self.table.insert(symbol, Operator { identity, combine });
+        self
+    }
+
+    /// Fold values with symbol's operator, or None if we don't know it
+    pub fn apply(&self, symbol: char, values: &[T]) -> Option<T> {
+        let operator = self.table.get(&symbol)?;
+        Some(
+            values
+                .iter()
+                .fold(operator.identity, |acc, val| (operator.combine)(acc, *val)),
+        )
+    }
+}
+
+/// `+` and `*`
+impl<T: Copy + Zero + One> Default for Operators<T> {
+    fn default() -> Self {
+        Operators::empty()
+            .with('+', T::zero(), Add::add)
+            .with('*', T::one(), Mul::mul)
+    }
+}
+
+/// One problem on the worksheet: the columns between two blank ones
+#[derive(Clone, Debug)]
+pub struct Block {
+    /// Where the block starts in the worksheet
+    pub first_col: usize,
+    /// Every row but the operator row
+    pub grid: Grid<char>,
+    pub operator: Option<char>,
+}
+
+impl Block {
+    /// Every number in the block, or None if something that isn't
+    /// blank doesn't parse
+    pub fn numbers<T: FromStr>(&self, read: ReadNumbers) -> Option<Vec<T>> {
+        let (lines, reversed): (Vec<Vec<char>>, bool) = match read {
+            ReadNumbers::ByRow { reversed } => (self.grid.rows().collect(), reversed),
+            ReadNumbers::ByCol { reversed } => (self.grid.cols().collect(), reversed),
+        };
+
+        let mut result: Vec<T> = lines
+            .into_iter()
+            .map(|line| line.into_iter().collect::<String>())
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.trim().parse().ok())
+            .collect::<Option<_>>()?;
+        if reversed {
+            result.reverse();
+        }
+        Some(result)
+    }
+
+    /// The block's operator applied to its numbers
+    pub fn evaluate<T: Copy + FromStr>(
+        &self,
+        read: ReadNumbers,
+        operators: &Operators<T>,
+    ) -> Option<T> {
+        operators.apply(self.operator?, &self.numbers(read)?)
+    }
+}
+
+/// A fixed-width page of problems side by side, separated by blank
+/// columns, with each problem's operator somewhere on the last line
+#[derive(Clone, Debug)]
+pub struct Worksheet {
+    pub grid: Grid<char>,
+}
+
+impl Worksheet {
+    /// Short lines are padded with spaces
+    pub fn from_lines(lines: impl Iterator<Item = String>) -> Self {
+        let lines: Vec<String> = lines.collect();
+        let width = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        Worksheet {
+            grid: Grid::from_line_iter(
+                lines
+                    .into_iter()
+                    .map(|line| format!("{:width$}", line, width = width)),
+            ),
+        }
+    }
+
+    /// Every block, left to right. A worksheet needs at least one row
+    /// of numbers above the operator row to have any
+    pub fn blocks(&self) -> Vec<Block> {
+        if self.grid.n_rows < 2 {
+            return vec![];
+        }
+        let is_blank = |col_idx: usize| self.grid.col(col_idx).iter().all(|c| c.is_whitespace());
+        let operator_row = self.grid.n_rows - 1;
+
+        let mut result: Vec<Block> = vec![];
+        let mut col_idx = 0;
+        while col_idx < self.grid.n_cols {
+            if is_blank(col_idx) {
+                col_idx += 1;
+                continue;
+            }
+
+            let first_col = col_idx;
+            while col_idx < self.grid.n_cols && !is_blank(col_idx) {
+                col_idx += 1;
+            }
+            let operator = self.grid.row(operator_row)[first_col..col_idx]
+                .iter()
+                .find(|c| !c.is_whitespace())
+                .copied();
+            result.push(Block {
+                first_col,
+                grid: self.grid.sub_grid(0..operator_row, first_col..col_idx),
+                operator,
+            });
+        }
+        result
+    }
+
+    /// Every block's answer, left to right, or None if any block can't
+    /// be worked out
+    pub fn evaluate<T: Copy + FromStr>(
+        &self,
+        read: ReadNumbers,
+        operators: &Operators<T>,
+    ) -> Option<Vec<T>> {
+        self.blocks()
+            .iter()
+            .map(|block| block.evaluate(read, operators))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Worksheet {
+        Worksheet::from_lines(
+            [
+                "123 328  51 64 ",
+                " 45 64  387 23",
+                "  6 98  215 314",
+                "*   +   *   +  ",
+            ]
+            .into_iter()
+            .map(|x| x.to_string()),
+        )
+    }
+
+    #[test]
+    fn test_blocks() {
+        let blocks = example().blocks();
+        assert_eq!(
+            blocks
+                .iter()
+                .map(|block| block.first_col)
+                .collect::<Vec<_>>(),
+            vec![0, 4, 8, 12]
+        );
+        assert_eq!(blocks[3].operator, Some('+'));
+        assert_eq!(
+            blocks[3].numbers::<u64>(ReadNumbers::ByRow { reversed: false }),
+            Some(vec![64, 23, 314])
+        );
+        assert_eq!(
+            blocks[3].numbers::<u64>(ReadNumbers::ByCol { reversed: true }),
+            Some(vec![4, 431, 623])
+        );
+        assert_eq!(
+            blocks[0].numbers::<u64>(ReadNumbers::ByCol { reversed: false }),
+            Some(vec![1, 24, 356])
+        );
+    }
+
+    #[test]
+    fn test_degenerate_worksheets() {
+        let lines =
+            |lines: &[&str]| Worksheet::from_lines(lines.iter().map(|x| x.to_string())).blocks();
+        assert!(lines(&[]).is_empty());
+        assert!(lines(&["*   +"]).is_empty());
+        assert!(lines(&["", ""]).is_empty());
+        assert_eq!(lines(&["12", "+"]).len(), 1);
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let worksheet = example();
+        let operators = Operators::<u64>::default();
+        let by_row = worksheet.evaluate(ReadNumbers::ByRow { reversed: false }, &operators);
+        assert_eq!(by_row.unwrap().iter().sum::<u64>(), 4277556);
+        let by_col = worksheet.evaluate(ReadNumbers::ByCol { reversed: true }, &operators);
+        assert_eq!(by_col.unwrap().iter().sum::<u64>(), 3263827);
+
+        let max_only = Operators::empty().with('+', 0u64, u64::max);
+        assert_eq!(
+            worksheet.blocks()[1].evaluate(ReadNumbers::ByRow { reversed: false }, &max_only),
+            Some(328)
+        );
+        assert_eq!(
+            worksheet.evaluate(ReadNumbers::ByRow { reversed: false }, &max_only),
+            None
+        );
+    }
+}
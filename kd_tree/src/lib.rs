@@ -0,0 +1,357 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::hash::Hash;
+
+use num::Integer;
+
+use coord_2d::Coord2D;
+use coord_3d::Coord3D;
+
+/// A point with a fixed number of axes that a KdTree can split on
+pub trait KdPoint: Clone {
+    type Scalar: Integer + Copy;
+    const DIMENSIONS: usize;
+
+    fn axis(&self, axis: usize) -> Self::Scalar;
+
+    fn squared_distance(&self, other: &Self) -> Self::Scalar;
+}
+
+impl<T: Integer + PartialOrd + Eq + Hash + Copy> KdPoint for Coord2D<T> {
+    type Scalar = T;
+    const DIMENSIONS: usize = 2;
+
+    fn axis(&self, axis: usize) -> T {
+        match axis {
+            0 => self.row,
+            1 => self.col,
+            _ => panic!("Coord2D has no axis {}", axis),
+        }
+    }
+
+    fn squared_distance(&self, other: &Self) -> T {
+        self.squared_euclidean_distance(other)
+    }
+}
+
+impl<T: Integer + PartialOrd + Eq + Hash + Copy> KdPoint for Coord3D<T> {
+    type Scalar = T;
+    const DIMENSIONS: usize = 3;
+
+    fn axis(&self, axis: usize) -> T {
+        match axis {
+            0 => self.x,
+            1 => self.y,
+            2 => self.z,
+            _ => panic!("Coord3D has no axis {}", axis),
+        }
+    }
+
+    fn squared_distance(&self, other: &Self) -> T {
+        self.squared_euclidean_distance(other)
+    }
+}
+
+/// Receives candidate points during a tree search; `bound` is the
+/// largest squared distance still worth visiting (None for any)
+trait Collector<S> {
+    fn offer(&mut self, idx: usize, squared_distance: S);
+
+    fn bound(&self) -> Option<S>;
+}
+
+struct KNearest<S: Ord> {
+    k: usize,
+    exclude: Option<usize>,
+    best: BinaryHeap<(S, usize)>,
+}
+
+impl<S: Ord + Copy> Collector<S> for KNearest<S> {
+    fn offer(&mut self, idx: usize, squared_distance: S) {
+        if self.exclude == Some(idx) || self.k == 0 {
+            return;
+        }
+
+        if self.best.len() < self.k {
+            self.best.push((squared_distance, idx));
+        } else if (squared_distance, idx) < *self.best.peek().unwrap() {
+            self.best.pop();
+            self.best.push((squared_distance, idx));
+        }
+    }
+
+    fn bound(&self) -> Option<S> {
+        if self.best.len() < self.k {
+            None
+        } else {
+            self.best.peek().map(|(d, _)| *d)
+        }
+    }
+}
+
+struct WithinRadius<S> {
+    squared_radius: S,
+    found: Vec<(S, usize)>,
+}
+
+impl<S: Ord + Copy> Collector<S> for WithinRadius<S> {
+    fn offer(&mut self, idx: usize, squared_distance: S) {
+        if squared_distance <= self.squared_radius {
+            self.found.push((squared_distance, idx));
+        }
+    }
+
+    fn bound(&self) -> Option<S> {
+        Some(self.squared_radius)
+    }
+}
+
+/// A static k-d tree. Query results refer to points by their
+/// index in the Vec the tree was built from
+#[derive(Debug, Clone)]
+pub struct KdTree<P: KdPoint> {
+    points: Vec<P>,
+    /// point indices laid out as an implicit tree: the middle element
+    /// of every sub-slice is the root of that subtree
+    order: Vec<usize>,
+}
+
+impl<P: KdPoint> KdTree<P> {
+    pub fn new(points: Vec<P>) -> Self {
+        let mut order: Vec<usize> = (0..points.len()).collect();
+        build(&points, &mut order, 0);
+        Self { points, order }
+    }
+
+    pub fn points(&self) -> &[P] {
+        &self.points
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// The indices and squared distances of the k points nearest to
+    /// query, nearest first (ties broken by index)
+    pub fn nearest(&self, query: &P, k: usize) -> Vec<(usize, P::Scalar)> {
+        self.nearest_excluding(query, k, None)
+    }
+
+    fn nearest_excluding(
+        &self,
+        query: &P,
+        k: usize,
+        exclude: Option<usize>,
+    ) -> Vec<(usize, P::Scalar)> {
+        let mut collector = KNearest {
+            k,
+            exclude,
+            best: BinaryHeap::new(),
+        };
+        self.search(query, 0, self.order.len(), 0, &mut collector);
+        collector
+            .best
+            .into_sorted_vec()
+            .into_iter()
+            .map(|(d, idx)| (idx, d))
+            .collect()
+    }
+
+    /// The indices and squared distances of every point whose squared
+    /// distance to query is at most squared_radius, nearest first
+    pub fn within(&self, query: &P, squared_radius: P::Scalar) -> Vec<(usize, P::Scalar)> {
+        let mut collector = WithinRadius {
+            squared_radius,
+            found: vec![],
+        };
+        self.search(query, 0, self.order.len(), 0, &mut collector);
+        collector.found.sort();
+        collector
+            .found
+            .into_iter()
+            .map(|(d, idx)| (idx, d))
+            .collect()
+    }
+
+    /// Every pair of points (i, j, squared distance) with i < j, closest
+    /// first, computed lazily so that taking the first few is cheap
+    pub fn closest_pairs(&self) -> ClosestPairs<'_, P> {
+        ClosestPairs::new(self)
+    }
+
+    fn search(
+        &self,
+        query: &P,
+        lo: usize,
+        hi: usize,
+        depth: usize,
+        collector: &mut impl Collector<P::Scalar>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+
+        let mid = lo + (hi - lo) / 2;
+        let idx = self.order[mid];
+        let point = &self.points[idx];
+        collector.offer(idx, query.squared_distance(point));
+
+        let axis = depth % P::DIMENSIONS;
+        let (q, p) = (query.axis(axis), point.axis(axis));
+        let (near, far) = if q < p {
+            ((lo, mid), (mid + 1, hi))
+        } else {
+            ((mid + 1, hi), (lo, mid))
+        };
+
+        self.search(query, near.0, near.1, depth + 1, collector);
+
+        let gap = if q > p { q - p } else { p - q };
+        if collector.bound().is_none_or(|bound| gap * gap <= bound) {
+            self.search(query, far.0, far.1, depth + 1, collector);
+        }
+    }
+}
+
+fn build<P: KdPoint>(points: &[P], order: &mut [usize], depth: usize) {
+    if order.len() <= 1 {
+        return;
+    }
+
+    let axis = depth % P::DIMENSIONS;
+    let mid = order.len() / 2;
+    order.select_nth_unstable_by_key(mid, |idx| points[*idx].axis(axis));
+
+    let (left, right) = order.split_at_mut(mid);
+    build(points, left, depth + 1);
+    build(points, &mut right[1..], depth + 1);
+}
+
+/// Lazily merges every point's nearest-neighbor list. Each pair shows up
+/// once from either end; we only yield it from its smaller index
+pub struct ClosestPairs<'a, P: KdPoint> {
+    tree: &'a KdTree<P>,
+    neighbors: Vec<Vec<(usize, P::Scalar)>>,
+    cursors: Vec<usize>,
+    heap: BinaryHeap<Reverse<(P::Scalar, usize, usize)>>,
+}
+
+impl<'a, P: KdPoint> ClosestPairs<'a, P> {
+    fn new(tree: &'a KdTree<P>) -> Self {
+        let n_points = tree.len();
+        let mut result = Self {
+            tree,
+            neighbors: vec![vec![]; n_points],
+            cursors: vec![0; n_points],
+            heap: BinaryHeap::new(),
+        };
+        for idx in 0..n_points {
+            result.push_next(idx);
+        }
+        result
+    }
+
+    /// Queue the next-nearest neighbor of idx, querying the tree for
+    /// twice as many neighbors whenever the cached ones run out
+    fn push_next(&mut self, idx: usize) {
+        let cursor = self.cursors[idx];
+        if cursor >= self.neighbors[idx].len() {
+            let n_others = self.tree.len() - 1;
+            if cursor >= n_others {
+                return;
+            }
+            let k = std::cmp::min(std::cmp::max(2 * cursor, 4), n_others);
+            self.neighbors[idx] = self
+                .tree
+                .nearest_excluding(&self.tree.points[idx], k, Some(idx));
+        }
+
+        let (other, d) = self.neighbors[idx][cursor];
+        self.heap.push(Reverse((d, idx, other)));
+    }
+}
+
+impl<P: KdPoint> Iterator for ClosestPairs<'_, P> {
+    type Item = (usize, usize, P::Scalar);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(Reverse((d, idx, other))) = self.heap.pop() {
+            self.cursors[idx] += 1;
+            self.push_next(idx);
+            if idx < other {
+                return Some((idx, other, d));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points() -> Vec<Coord3D<usize>> {
+        // a small deterministic scatter with some repeated distances
+        (0..60usize)
+            .map(|i| Coord3D::new((i * 37) % 23, (i * 11) % 17, (i * 5) % 13))
+            .collect()
+    }
+
+    #[test]
+    fn test_nearest_and_within() {
+        let points = points();
+        let tree = KdTree::new(points.clone());
+        let query = Coord3D::new(7, 3, 9);
+
+        let mut brute: Vec<(usize, usize)> = points
+            .iter()
+            .enumerate()
+            .map(|(idx, p)| (p.squared_euclidean_distance(&query), idx))
+            .collect();
+        brute.sort();
+
+        assert_eq!(
+            tree.nearest(&query, 7),
+            brute[..7]
+                .iter()
+                .map(|(d, idx)| (*idx, *d))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            tree.within(&query, 30),
+            brute
+                .iter()
+                .filter(|(d, _)| *d <= 30)
+                .map(|(d, idx)| (*idx, *d))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_closest_pairs() {
+        let points: Vec<Coord2D<i64>> = (0..40i64)
+            .map(|i| Coord2D::new((i * 13) % 29 - 10, (i * 7) % 19))
+            .collect();
+        let tree = KdTree::new(points.clone());
+
+        let mut brute: Vec<(i64, usize, usize)> = vec![];
+        for i in 0..points.len() {
+            for j in (i + 1)..points.len() {
+                brute.push((points[i].squared_euclidean_distance(&points[j]), i, j));
+            }
+        }
+        brute.sort();
+
+        assert_eq!(
+            tree.closest_pairs().collect::<Vec<_>>(),
+            brute
+                .into_iter()
+                .map(|(d, i, j)| (i, j, d))
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use std::hash::Hash;
+
+use num::{Integer, ToPrimitive};
+
+use coord_2d::Coord2D;
+use rect::Rect;
+use span_1d::Span1D;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Orientation {
+    Horizontal,
+    Vertical,
+}
+
+/// An axis-aligned segment between two lattice points (inclusive)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edge<T: Integer + PartialOrd + Eq + Hash + Copy> {
+    pub start: Coord2D<T>,
+    pub end: Coord2D<T>,
+    pub orientation: Orientation,
+}
+
+impl<T: Integer + Copy + Hash + TryFrom<usize> + ToPrimitive> Edge<T>
+where
+    <T as TryFrom<usize>>::Error: std::fmt::Debug,
+{
+    /// None if the points are equal or don't share a row or column
+    pub fn from_points(start: Coord2D<T>, end: Coord2D<T>) -> Option<Self> {
+        let orientation = if start == end {
+            return None;
+        } else if start.row == end.row {
+            Orientation::Horizontal
+        } else if start.col == end.col {
+            Orientation::Vertical
+        } else {
+            return None;
+        };
+        Some(Self {
+            start,
+            end,
+            orientation,
+        })
+    }
+
+    pub fn row_span(&self) -> Span1D<T> {
+        Span1D::from_start_end_inclusive(self.start.row, self.end.row)
+    }
+
+    pub fn col_span(&self) -> Span1D<T> {
+        Span1D::from_start_end_inclusive(self.start.col, self.end.col)
+    }
+
+    /// The edge as a one-wide rectangle of lattice points
+    pub fn as_rect(&self) -> Rect<T> {
+        Rect::new(self.row_span(), self.col_span())
+    }
+
+    /// The number of unit steps from start to end
+    pub fn n_steps(&self) -> usize {
+        self.row_span().len + self.col_span().len - 2
+    }
+
+    pub fn contains(&self, coord: &Coord2D<T>) -> bool {
+        self.as_rect().contains(coord)
+    }
+
+    /// Do perpendicular edges cross through each other's interiors?
+    /// Touching at (or ending on) an endpoint doesn't count
+    pub fn crosses(&self, other: &Self) -> bool {
+        match (self.orientation, other.orientation) {
+            (Orientation::Horizontal, Orientation::Vertical) => {
+                other.row_span().shrink(1).contains(self.start.row)
+                    && self.col_span().shrink(1).contains(other.start.col)
+            }
+            (Orientation::Vertical, Orientation::Horizontal) => other.crosses(self),
+            _ => false,
+        }
+    }
+
+    /// Do these edges share an orientation and some lattice points?
+    pub fn is_parallel_with_overlap(&self, other: &Self) -> bool {
+        self.orientation == other.orientation && self.as_rect().intersects(&other.as_rect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crosses() {
+        //   ^
+        //   |
+        // <-+>
+        //   v
+        let hz: Edge<usize> = Edge::from_points(Coord2D::new(2, 0), Coord2D::new(2, 3)).unwrap();
+        let vl = Edge::from_points(Coord2D::new(0, 2), Coord2D::new(3, 2)).unwrap();
+        assert!(hz.crosses(&vl));
+        assert!(vl.crosses(&hz));
+
+        //
+        //
+        // <-+>
+        //   |
+        //   v
+        let hz: Edge<usize> = Edge::from_points(Coord2D::new(2, 0), Coord2D::new(2, 3)).unwrap();
+        let vl = Edge::from_points(Coord2D::new(2, 2), Coord2D::new(3, 2)).unwrap();
+        assert!(!hz.crosses(&vl));
+        assert!(!vl.crosses(&hz));
+
+        let hz: Edge<usize> = Edge::from_points(Coord2D::new(5, 9), Coord2D::new(5, 2)).unwrap();
+        let right = Edge::from_points(Coord2D::new(3, 9), Coord2D::new(5, 9)).unwrap();
+        assert!(!hz.crosses(&right));
+    }
+
+    #[test]
+    fn test_parallel_with_overlap() {
+        let a: Edge<i32> = Edge::from_points(Coord2D::new(0, 0), Coord2D::new(0, 5)).unwrap();
+        let b = Edge::from_points(Coord2D::new(0, 7), Coord2D::new(0, 5)).unwrap();
+        let c = Edge::from_points(Coord2D::new(1, 0), Coord2D::new(1, 5)).unwrap();
+        assert!(a.is_parallel_with_overlap(&b));
+        assert!(!a.is_parallel_with_overlap(&c));
+        assert_eq!(a.n_steps(), 5);
+        assert!(Edge::from_points(Coord2D::new(0, 0), Coord2D::new(1, 1)).is_none());
+    }
+}
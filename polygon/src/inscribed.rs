@@ -0,0 +1,109 @@
+use std::hash::Hash;
+
+use num::{CheckedAdd, CheckedSub, Integer, ToPrimitive};
+
+use coord_2d::Coord2D;
+use grid::{Grid, PrefixSum2D};
+use rect::Rect;
+use utils::CoordinateCompressor;
+
+use crate::RectilinearPolygon;
+
+impl<T> RectilinearPolygon<T>
+where
+    T: Integer + Copy + Hash + TryFrom<usize> + ToPrimitive + CheckedAdd + CheckedSub,
+    <T as TryFrom<usize>>::Error: std::fmt::Debug,
+{
+    /// The largest rectangle (by lattice points) that has two vertices
+    /// as opposite corners and lies entirely inside the polygon.
+    ///
+    /// Every row strictly between two consecutive vertex rows looks the
+    /// same, and likewise for columns, so we squash each such run down to
+    /// a single cell. Then a prefix sum over the cells lying outside the
+    /// polygon tells us in O(1) whether a candidate is fully inside
+    pub fn largest_inscribed_rectangle(&self) -> Option<Rect<T>> {
+        let rows = CoordinateCompressor::with_neighbors(self.vertices.iter().map(|v| v.row));
+        let cols = CoordinateCompressor::with_neighbors(self.vertices.iter().map(|v| v.col));
+
+        let outside = PrefixSum2D::new(
+            &Grid::new(
+                rows.values()
+                    .iter()
+                    .map(|row| {
+                        let coverage = self.row_coverage(*row);
+                        cols.values()
+                            .iter()
+                            .map(|col| !coverage.contains(*col))
+                            .collect()
+                    })
+                    .collect(),
+            ),
+            usize::from,
+        );
+
+        let row_index = |row: T| rows.compress(row).unwrap();
+        let col_index = |col: T| cols.compress(col).unwrap();
+
+        let mut best: Option<Rect<T>> = None;
+        for (idx, a) in self.vertices.iter().enumerate() {
+            for b in self.vertices[(idx + 1)..].iter() {
+                let candidate = Rect::from_corners(a, b);
+                if best
+                    .as_ref()
+                    .is_some_and(|best| best.area() >= candidate.area())
+                {
+                    continue;
+                }
+
+                let corner_1 = Coord2D::new(row_index(a.row), col_index(a.col));
+                let corner_2 = Coord2D::new(row_index(b.row), col_index(b.col));
+                if outside.sum(&corner_1, &corner_2) == 0 {
+                    best = Some(candidate);
+                }
+            }
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polygon(points: &[(i64, i64)]) -> RectilinearPolygon<i64> {
+        RectilinearPolygon::new(points.iter().map(|(r, c)| Coord2D::new(*r, *c)).collect()).unwrap()
+    }
+
+    #[test]
+    fn test_largest_inscribed_rectangle() {
+        // the day 9 example, with (row, col) swapped from the puzzle's (x, y)
+        let example = polygon(&[
+            (1, 7),
+            (1, 11),
+            (7, 11),
+            (7, 9),
+            (5, 9),
+            (5, 2),
+            (3, 2),
+            (3, 7),
+        ]);
+        let best = example.largest_inscribed_rectangle().unwrap();
+        assert_eq!(best.area(), 24);
+        assert!(example.contains_rect(&best));
+
+        // a U shape: the bounding box spans the notch, but the legs fit
+        let u_shape = polygon(&[
+            (0, 0),
+            (0, 3),
+            (10, 3),
+            (10, 6),
+            (0, 6),
+            (0, 9),
+            (12, 9),
+            (12, 0),
+        ]);
+        let best = u_shape.largest_inscribed_rectangle().unwrap();
+        assert_eq!(best.area(), 52);
+        assert_eq!(best.cols.len, 4);
+    }
+}
@@ -0,0 +1,281 @@
+use std::hash::Hash;
+
+use num::{Integer, ToPrimitive};
+use thiserror::Error;
+
+use coord_2d::Coord2D;
+use rect::Rect;
+use span_1d::{Span1D, SpanSet};
+
+mod edge;
+mod inscribed;
+
+pub use edge::{Edge, Orientation};
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum PolygonError {
+    #[error("a polygon needs at least 4 vertices, found {0}")]
+    TooFewVertices(usize),
+    #[error("vertex {0} and the next one don't share a row or column")]
+    NotAxisAligned(usize),
+    #[error("vertex {0} is repeated by the next one")]
+    RepeatedVertex(usize),
+    #[error("edges {0} and {1} intersect")]
+    SelfIntersecting(usize, usize),
+}
+
+/// A simple polygon whose edges are all horizontal or vertical, with
+/// vertices on lattice points. Each vertex is joined to the next one,
+/// and the last to the first. A lattice point counts as inside if it
+/// is in the interior or on the boundary
+#[derive(Debug, Clone)]
+pub struct RectilinearPolygon<T: Integer + PartialOrd + Eq + Hash + Copy> {
+    vertices: Vec<Coord2D<T>>,
+    edges: Vec<Edge<T>>,
+}
+
+impl<T: Integer + Copy + Hash + TryFrom<usize> + ToPrimitive> RectilinearPolygon<T>
+where
+    <T as TryFrom<usize>>::Error: std::fmt::Debug,
+{
+    pub fn new(vertices: Vec<Coord2D<T>>) -> Result<Self, PolygonError> {
+        let n_vertices = vertices.len();
+        if n_vertices < 4 {
+            return Err(PolygonError::TooFewVertices(n_vertices));
+        }
+
+        let edges = (0..n_vertices)
+            .map(|idx| {
+                let (start, end) = (&vertices[idx], &vertices[(idx + 1) % n_vertices]);
+                if start == end {
+                    Err(PolygonError::RepeatedVertex(idx))
+                } else {
+                    Edge::from_points(start.clone(), end.clone())
+                        .ok_or(PolygonError::NotAxisAligned(idx))
+                }
+            })
+            .collect::<Result<Vec<Edge<T>>, PolygonError>>()?;
+
+        // neighboring edges may only share their common vertex;
+        // every other pair must be disjoint
+        let rects: Vec<Rect<T>> = edges.iter().map(|edge| edge.as_rect()).collect();
+        for idx_1 in 0..n_vertices {
+            for idx_2 in (idx_1 + 1)..n_vertices {
+                let adjacent = idx_2 == idx_1 + 1 || (idx_1 == 0 && idx_2 == n_vertices - 1);
+                let shared = rects[idx_1]
+                    .intersection(&rects[idx_2])
+                    .map_or(0, |overlap| overlap.area());
+                if shared > usize::from(adjacent) {
+                    return Err(PolygonError::SelfIntersecting(idx_1, idx_2));
+                }
+            }
+        }
+
+        Ok(Self { vertices, edges })
+    }
+
+    pub fn vertices(&self) -> &[Coord2D<T>] {
+        &self.vertices
+    }
+
+    pub fn edges(&self) -> &[Edge<T>] {
+        &self.edges
+    }
+
+    pub fn on_boundary(&self, coord: &Coord2D<T>) -> bool {
+        self.edges.iter().any(|edge| edge.contains(coord))
+    }
+
+    pub fn contains(&self, coord: &Coord2D<T>) -> bool {
+        if self.on_boundary(coord) {
+            return true;
+        }
+
+        // cast a ray towards increasing columns; vertical edges count
+        // as crossed if they cover the ray's row, half-open at the bottom
+        let n_crossings = self
+            .edges
+            .iter()
+            .filter(|edge| {
+                edge.orientation == Orientation::Vertical
+                    && edge.start.col > coord.col
+                    && edge.row_span().contains(coord.row)
+                    && edge.row_span().last() != Some(coord.row)
+            })
+            .count();
+        n_crossings % 2 == 1
+    }
+
+    /// The columns of every lattice point inside the polygon on this row
+    pub fn row_coverage(&self, row: T) -> SpanSet<T> {
+        let mut crossings: Vec<T> = vec![];
+        let mut coverage: SpanSet<T> = SpanSet::new();
+        for edge in self.edges.iter() {
+            let (rows, cols) = (edge.row_span(), edge.col_span());
+            if !rows.contains(row) {
+                continue;
+            }
+            // points on the boundary are inside
+            coverage.insert(cols);
+            if edge.orientation == Orientation::Vertical && rows.last() != Some(row) {
+                crossings.push(edge.start.col);
+            }
+        }
+
+        crossings.sort();
+        for pair in crossings.chunks(2) {
+            coverage.insert(Span1D::from_start_end_inclusive(pair[0], pair[1]));
+        }
+        coverage
+    }
+
+    /// The area enclosed by the vertex loop, by the shoelace formula
+    pub fn area(&self) -> usize {
+        let twice_area: i128 = self
+            .edges
+            .iter()
+            .map(|edge| {
+                let (r_1, c_1) = (
+                    edge.start.row.to_i128().unwrap(),
+                    edge.start.col.to_i128().unwrap(),
+                );
+                let (r_2, c_2) = (
+                    edge.end.row.to_i128().unwrap(),
+                    edge.end.col.to_i128().unwrap(),
+                );
+                r_1 * c_2 - r_2 * c_1
+            })
+            .sum();
+        (twice_area.abs() / 2) as usize
+    }
+
+    /// The number of lattice points on the boundary
+    pub fn boundary_points(&self) -> usize {
+        self.edges.iter().map(|edge| edge.n_steps()).sum()
+    }
+
+    /// The number of lattice points strictly inside, by Pick's theorem
+    pub fn interior_points(&self) -> usize {
+        self.area() + 1 - self.boundary_points() / 2
+    }
+
+    /// The number of lattice points inside or on the boundary
+    pub fn lattice_points(&self) -> usize {
+        self.interior_points() + self.boundary_points()
+    }
+
+    /// Is every lattice point of rect inside the polygon? Rows between
+    /// consecutive vertex rows all look alike, so we only need to check
+    /// the rect's first row, plus each vertex row and the row after it
+    pub fn contains_rect(&self, rect: &Rect<T>) -> bool {
+        if rect.is_empty() {
+            return true;
+        }
+
+        let mut rows: Vec<T> = vec![rect.rows.start];
+        for vertex in self.vertices.iter() {
+            rows.extend([vertex.row, vertex.row + T::one()]);
+        }
+        rows.sort();
+        rows.dedup();
+
+        let needed: SpanSet<T> = SpanSet::from_iter([rect.cols.clone()]);
+        rows.into_iter()
+            .filter(|row| rect.rows.contains(*row))
+            .all(|row| needed.difference(&self.row_coverage(row)).is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// the day 9 example
+    ///
+    ///   ..............
+    ///   .......#XXX#..
+    ///   .......XXXXX..
+    ///   ..#XXXX#XXXX..
+    ///   ..XXXXXXXXXX..
+    ///   ..#XXXXXX#XX..
+    ///   .........XXX..
+    ///   .........#X#..
+    fn example() -> RectilinearPolygon<usize> {
+        RectilinearPolygon::new(
+            [
+                (1, 7),
+                (1, 11),
+                (7, 11),
+                (7, 9),
+                (5, 9),
+                (5, 2),
+                (3, 2),
+                (3, 7),
+            ]
+            .into_iter()
+            .map(|(row, col)| Coord2D::new(row, col))
+            .collect(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_validation() {
+        let square = |points: &[(i32, i32)]| {
+            RectilinearPolygon::new(points.iter().map(|(r, c)| Coord2D::new(*r, *c)).collect())
+        };
+        assert!(square(&[(0, 0), (0, 3), (3, 3), (3, 0)]).is_ok());
+        assert_eq!(
+            square(&[(0, 0), (0, 3), (3, 3)]).err(),
+            Some(PolygonError::TooFewVertices(3))
+        );
+        assert_eq!(
+            square(&[(0, 0), (0, 3), (3, 4), (3, 0)]).err(),
+            Some(PolygonError::NotAxisAligned(1))
+        );
+        assert_eq!(
+            square(&[(0, 0), (0, 3), (0, 3), (3, 3), (3, 0)]).err(),
+            Some(PolygonError::RepeatedVertex(1))
+        );
+        // a bow tie
+        assert!(square(&[(0, 0), (0, 3), (3, 3), (3, 1), (-1, 1), (-1, 0)]).is_err());
+        // doubling back along an edge
+        assert!(square(&[(0, 0), (0, 3), (0, 2), (2, 2), (2, 0)]).is_err());
+    }
+
+    #[test]
+    fn test_lattice_counts() {
+        let polygon = example();
+        assert_eq!(polygon.boundary_points(), 30);
+        assert_eq!(polygon.area(), 30);
+        assert_eq!(polygon.interior_points(), 16);
+        assert_eq!(polygon.lattice_points(), 46);
+
+        let brute = Rect::from_corners(&Coord2D::new(0, 0), &Coord2D::new(8, 13))
+            .coords()
+            .filter(|coord| polygon.contains(coord))
+            .count();
+        assert_eq!(brute, 46);
+        for row in 0..9 {
+            assert_eq!(
+                polygon.row_coverage(row).covered_len(),
+                (0..14)
+                    .filter(|col| polygon.contains(&Coord2D::new(row, *col)))
+                    .count()
+            );
+        }
+    }
+
+    #[test]
+    fn test_contains_rect() {
+        let polygon = example();
+        let rect = |a: (usize, usize), b: (usize, usize)| {
+            Rect::from_corners(&Coord2D::new(a.0, a.1), &Coord2D::new(b.0, b.1))
+        };
+        assert!(polygon.contains_rect(&rect((3, 2), (5, 9))));
+        assert!(polygon.contains_rect(&rect((1, 7), (5, 11))));
+        assert!(!polygon.contains_rect(&rect((1, 7), (7, 11))));
+        assert!(polygon.contains_rect(&rect((3, 2), (5, 11))));
+        assert!(!polygon.contains_rect(&rect((1, 2), (5, 9))));
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::BTreeSet;
+
+use coord_2d::Coord2D;
+use grid::Grid;
+
+mod packing;
+
+pub use packing::pack;
+
+/// A set of unit cells, shifted so the topmost row and leftmost column
+/// are both 0. Cells are kept sorted row by row
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Polyomino {
+    cells: Vec<Coord2D<usize>>,
+}
+
+impl Polyomino {
+    pub fn new(cells: impl IntoIterator<Item = Coord2D<isize>>) -> Self {
+        let cells: Vec<Coord2D<isize>> = cells.into_iter().collect();
+        let min_row = cells.iter().map(|cell| cell.row).min().unwrap_or(0);
+        let min_col = cells.iter().map(|cell| cell.col).min().unwrap_or(0);
+
+        let cells: BTreeSet<(usize, usize)> = cells
+            .into_iter()
+            .map(|cell| ((cell.row - min_row) as usize, (cell.col - min_col) as usize))
+            .collect();
+        Self {
+            cells: cells
+                .into_iter()
+                .map(|(row, col)| Coord2D::new(row, col))
+                .collect(),
+        }
+    }
+
+    /// The cells of grid holding a '#'
+    pub fn from_grid(grid: &Grid<char>) -> Self {
+        Self::new(
+            grid.find('#')
+                .into_iter()
+                .map(|cell| Coord2D::new(cell.row as isize, cell.col as isize)),
+        )
+    }
+
+    pub fn cells(&self) -> &[Coord2D<usize>] {
+        &self.cells
+    }
+
+    pub fn n_cells(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn n_rows(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|cell| cell.row + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn n_cols(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|cell| cell.col + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// A quarter turn clockwise
+    pub fn rotate(&self) -> Self {
+        Self::new(
+            self.cells
+                .iter()
+                .map(|cell| Coord2D::new(cell.col as isize, -(cell.row as isize))),
+        )
+    }
+
+    /// Mirror left to right
+    pub fn flip(&self) -> Self {
+        Self::new(
+            self.cells
+                .iter()
+                .map(|cell| Coord2D::new(cell.row as isize, -(cell.col as isize))),
+        )
+    }
+
+    /// Every distinct shape reachable by rotating and flipping
+    pub fn orientations(&self) -> Vec<Self> {
+        let mut result: Vec<Self> = vec![];
+        let mut current = self.clone();
+        for _ in 0..4 {
+            for candidate in [current.clone(), current.flip()] {
+                if !result.contains(&candidate) {
+                    result.push(candidate);
+                }
+            }
+            current = current.rotate();
+        }
+        result
+    }
+
+    pub fn to_grid(&self) -> Grid<char> {
+        let mut result = Grid::new(vec![vec!['.'; self.n_cols()]; self.n_rows()]);
+        for cell in self.cells.iter() {
+            result.set('#', cell.row, cell.col);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_rows(rows: &[&str]) -> Polyomino {
+        Polyomino::from_grid(&Grid::from_line_iter(rows.iter().map(|x| x.to_string())))
+    }
+
+    #[test]
+    fn test_orientations() {
+        // the L tromino and S tetromino don't have all 8 distinct orientations
+        assert_eq!(from_rows(&["#.", "##"]).orientations().len(), 4);
+        assert_eq!(from_rows(&[".##", "##."]).orientations().len(), 4);
+        assert_eq!(from_rows(&["###", "#..", "###"]).orientations().len(), 4);
+        assert_eq!(from_rows(&["###", "##.", "##."]).orientations().len(), 8);
+        assert_eq!(from_rows(&["#.#", "###", "#.#"]).orientations().len(), 2);
+
+        let l = from_rows(&["#..", "###"]);
+        assert_eq!(l.rotate(), from_rows(&["##", "#.", "#."]));
+        assert_eq!(l.flip(), from_rows(&["..#", "###"]));
+        assert_eq!(l.n_cells(), 4);
+    }
+}
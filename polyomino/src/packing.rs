@@ -0,0 +1,243 @@
+use grid::Grid;
+
+use crate::Polyomino;
+
+/// A shape in one orientation, as (row, col) offsets from its first
+/// cell in reading order. That first cell is the one we anchor on the
+/// first free board cell, so col offsets may be negative
+type Offsets = Vec<(usize, isize)>;
+
+/// What pieces get drawn with. Each piece takes the first label none of
+/// its neighbors has, so touching pieces always differ unless a piece
+/// has more neighbors than there are labels
+const LABELS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+struct Packer {
+    n_rows: usize,
+    n_cols: usize,
+    /// per piece kind, every distinct orientation
+    orientations: Vec<Vec<Offsets>>,
+    remaining: Vec<usize>,
+    /// None for a free cell, otherwise the label of whatever fills it
+    board: Vec<Option<char>>,
+}
+
+impl Packer {
+    /// The board cells the shape would cover when anchored at pos,
+    /// if they're all on the board and free
+    fn cells_for(&self, pos: usize, offsets: &Offsets) -> Option<Vec<usize>> {
+        let (row, col) = (pos / self.n_cols, pos % self.n_cols);
+        offsets
+            .iter()
+            .map(|(d_row, d_col)| {
+                let (row, col) = (row + d_row, col as isize + d_col);
+                if row >= self.n_rows || col < 0 || col as usize >= self.n_cols {
+                    return None;
+                }
+                let idx = row * self.n_cols + col as usize;
+                self.board[idx].is_none().then_some(idx)
+            })
+            .collect()
+    }
+
+    /// A label that no piece touching cells already has
+    fn label_for(&self, cells: &[usize]) -> char {
+        let mut taken: Vec<char> = vec![];
+        for idx in cells.iter() {
+            let (row, col) = (idx / self.n_cols, idx % self.n_cols);
+            let neighbors = [
+                (row > 0).then(|| idx - self.n_cols),
+                (row + 1 < self.n_rows).then(|| idx + self.n_cols),
+                (col > 0).then(|| idx - 1),
+                (col + 1 < self.n_cols).then(|| idx + 1),
+            ];
+            taken.extend(
+                neighbors
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|neighbor| self.board[neighbor])
+                    .filter(|label| *label != '.'),
+            );
+        }
+        LABELS
+            .chars()
+            .find(|label| !taken.contains(label))
+            .unwrap_or('?')
+    }
+
+    /// Fill the board from pos onwards. Every free cell must either be
+    /// covered by a piece anchored there, or be given up as a gap; we
+    /// can afford at most n_gaps more gaps
+    fn search(&mut self, pos: usize, n_gaps: usize) -> bool {
+        if self.remaining.iter().all(|n| *n == 0) {
+            return true;
+        }
+        let pos = match (pos..self.board.len()).find(|idx| self.board[*idx].is_none()) {
+            Some(pos) => pos,
+            None => return false,
+        };
+
+        for kind in 0..self.orientations.len() {
+            if self.remaining[kind] == 0 {
+                continue;
+            }
+            for orientation in 0..self.orientations[kind].len() {
+                let cells = match self.cells_for(pos, &self.orientations[kind][orientation]) {
+                    Some(cells) => cells,
+                    None => continue,
+                };
+
+                let label = self.label_for(&cells);
+                for idx in cells.iter() {
+                    self.board[*idx] = Some(label);
+                }
+                self.remaining[kind] -= 1;
+
+                if self.search(pos + 1, n_gaps) {
+                    return true;
+                }
+
+                self.remaining[kind] += 1;
+                for idx in cells.iter() {
+                    self.board[*idx] = None;
+                }
+            }
+        }
+
+        if n_gaps > 0 {
+            self.board[pos] = Some('.');
+            if self.search(pos + 1, n_gaps - 1) {
+                return true;
+            }
+            self.board[pos] = None;
+        }
+        false
+    }
+}
+
+/// Try to fit `count` copies of each shape onto an n_rows x n_cols board
+/// without overlap, rotating and flipping as needed. On success, returns
+/// the board with each piece drawn in a letter (see LABELS) and gaps
+/// as '.'. Empty shapes take no room, so they're ignored
+pub fn pack(n_rows: usize, n_cols: usize, pieces: &[(Polyomino, usize)]) -> Option<Grid<char>> {
+    let pieces: Vec<(Polyomino, usize)> = pieces
+        .iter()
+        .filter(|(shape, _)| shape.n_cells() > 0)
+        .cloned()
+        .collect();
+    let needed: usize = pieces
+        .iter()
+        .map(|(shape, count)| shape.n_cells() * count)
+        .sum();
+    if needed > n_rows * n_cols {
+        return None;
+    }
+    if n_rows == 0 || n_cols == 0 || needed == 0 {
+        return Some(Grid::new(vec![vec!['.'; n_cols]; n_rows]));
+    }
+    if n_cols > n_rows {
+        // we fill in reading order, which goes much faster when the
+        // rows are short. Shapes are flipped every which way anyway,
+        // so we can solve the transposed board and transpose back
+        let packed = pack(n_cols, n_rows, &pieces)?;
+        return Some(Grid::new(packed.cols().collect()));
+    }
+
+    let orientations = pieces
+        .iter()
+        .map(|(shape, _)| {
+            shape
+                .orientations()
+                .into_iter()
+                .map(|orientation| {
+                    let anchor = &orientation.cells()[0];
+                    orientation
+                        .cells()
+                        .iter()
+                        .map(|cell| {
+                            (
+                                cell.row - anchor.row,
+                                cell.col as isize - anchor.col as isize,
+                            )
+                        })
+                        .collect()
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut packer = Packer {
+        n_rows,
+        n_cols,
+        orientations,
+        remaining: pieces.iter().map(|(_, count)| *count).collect(),
+        board: vec![None; n_rows * n_cols],
+    };
+    if !packer.search(0, n_rows * n_cols - needed) {
+        return None;
+    }
+
+    Some(Grid::new(
+        packer
+            .board
+            .chunks(n_cols)
+            .map(|row| row.iter().map(|cell| cell.unwrap_or('.')).collect())
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shape(rows: &[&str]) -> Polyomino {
+        Polyomino::from_grid(&Grid::from_line_iter(rows.iter().map(|x| x.to_string())))
+    }
+
+    #[test]
+    fn test_pack() {
+        let l = shape(&["#.", "#.", "##"]);
+        let packed = pack(2, 4, &[(l.clone(), 2)]).unwrap();
+        assert_eq!((packed.n_rows, packed.n_cols), (2, 4));
+        assert_eq!(packed.find('.').len(), 0);
+        assert_eq!(packed.find('A').len(), 4);
+        assert_eq!(packed.find('B').len(), 4);
+
+        assert!(pack(3, 3, &[(l.clone(), 2)]).is_some());
+        assert!(pack(2, 4, &[(shape(&["#.", "##"]), 2), (l.clone(), 1)]).is_none());
+        assert!(pack(3, 3, &[(shape(&["#.#", "###"]), 1), (l, 1)]).is_none());
+    }
+
+    #[test]
+    fn test_empty_and_crowded_boards() {
+        let packed = pack(0, 5, &[]).unwrap();
+        assert_eq!(packed.n_rows, 0);
+        assert!(pack(0, 5, &[(shape(&["#"]), 1)]).is_none());
+        assert!(pack(4, 0, &[(shape(&["#"]), 0)]).is_some());
+
+        let packed = pack(2, 2, &[(Polyomino::new([]), 1), (shape(&["##"]), 1)]).unwrap();
+        assert_eq!(packed.find('.').len(), 2);
+
+        // 40 dominoes: more pieces than letters in the alphabet, but
+        // touching ones still never share a label
+        let packed = pack(8, 10, &[(shape(&["##"]), 40)]).unwrap();
+        assert!(packed.find('?').is_empty());
+        for row in 0..8 {
+            for col in 0..10 {
+                let here = packed.row(row)[col];
+                let n_same = [
+                    (row > 0).then(|| packed.row(row - 1)[col]),
+                    (row < 7).then(|| packed.row(row + 1)[col]),
+                    (col > 0).then(|| packed.row(row)[col - 1]),
+                    (col < 9).then(|| packed.row(row)[col + 1]),
+                ]
+                .into_iter()
+                .flatten()
+                .filter(|other| *other == here)
+                .count();
+                // a domino cell shares its label with exactly its partner
+                assert_eq!(n_same, 1);
+            }
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use std::hash::Hash;
+
+use num::iter::range;
+use num::{Integer, ToPrimitive};
+
+use coord_3d::Coord3D;
+use span_1d::Span1D;
+
+use crate::Rect;
+
+/// An axis-aligned box of lattice points: every (x, y, z) with
+/// each component in the matching span
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cuboid<T: Integer + Copy + TryFrom<usize>>
+where
+    <T as TryFrom<usize>>::Error: std::fmt::Debug,
+{
+    pub x: Span1D<T>,
+    pub y: Span1D<T>,
+    pub z: Span1D<T>,
+}
+
+impl<T: Integer + Copy + Hash + TryFrom<usize> + ToPrimitive> Cuboid<T>
+where
+    <T as TryFrom<usize>>::Error: std::fmt::Debug,
+{
+    pub fn new(x: Span1D<T>, y: Span1D<T>, z: Span1D<T>) -> Self {
+        Self { x, y, z }
+    }
+
+    /// The cuboid in which the two points are opposite corners (inclusive)
+    pub fn from_corners(a: &Coord3D<T>, b: &Coord3D<T>) -> Self {
+        Self::new(
+            Span1D::from_start_end_inclusive(a.x, b.x),
+            Span1D::from_start_end_inclusive(a.y, b.y),
+            Span1D::from_start_end_inclusive(a.z, b.z),
+        )
+    }
+
+    pub fn volume(&self) -> usize {
+        self.x.len * self.y.len * self.z.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.x.is_empty() || self.y.is_empty() || self.z.is_empty()
+    }
+
+    pub fn contains(&self, coord: &Coord3D<T>) -> bool {
+        self.x.contains(coord.x) && self.y.contains(coord.y) && self.z.contains(coord.z)
+    }
+
+    /// Whether every point of other is in self
+    pub fn contains_cuboid(&self, other: &Self) -> bool {
+        other.is_empty() || self.intersection(other).as_ref() == Some(other)
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.x.intersects(&other.x) && self.y.intersects(&other.y) && self.z.intersects(&other.z)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        Some(Self::new(
+            self.x.intersection(&other.x)?,
+            self.y.intersection(&other.y)?,
+            self.z.intersection(&other.z)?,
+        ))
+    }
+
+    /// The points of self that are not in other, as at most six
+    /// disjoint cuboids
+    pub fn subtract(&self, other: &Self) -> Vec<Self> {
+        let overlap = match self.intersection(other) {
+            Some(overlap) => overlap,
+            None => return vec![self.clone()],
+        };
+
+        let (x_before, rest) = self.x.split_at(overlap.x.start);
+        let (x_middle, x_after) = rest.split_at(overlap.x.end());
+        let (y_before, rest) = self.y.split_at(overlap.y.start);
+        let (y_middle, y_after) = rest.split_at(overlap.y.end());
+        let (z_before, rest) = self.z.split_at(overlap.z.start);
+        let (_, z_after) = rest.split_at(overlap.z.end());
+
+        [
+            Self::new(x_before, self.y.clone(), self.z.clone()),
+            Self::new(x_after, self.y.clone(), self.z.clone()),
+            Self::new(x_middle.clone(), y_before, self.z.clone()),
+            Self::new(x_middle.clone(), y_after, self.z.clone()),
+            Self::new(x_middle.clone(), y_middle.clone(), z_before),
+            Self::new(x_middle, y_middle, z_after),
+        ]
+        .into_iter()
+        .filter(|piece| !piece.is_empty())
+        .collect()
+    }
+
+    /// Every point in the cuboid, with z varying fastest
+    pub fn coords(&self) -> impl Iterator<Item = Coord3D<T>> + '_ {
+        range(self.x.start, self.x.end()).flat_map(move |x| {
+            range(self.y.start, self.y.end()).flat_map(move |y| {
+                range(self.z.start, self.z.end()).map(move |z| Coord3D::new(x, y, z))
+            })
+        })
+    }
+
+    /// The number of points covered by at least one of cuboids. Sweeps
+    /// over x, measuring the covered (y, z) area in each slab
+    pub fn union_volume(cuboids: &[Self]) -> usize {
+        let mut boundaries: Vec<T> = cuboids
+            .iter()
+            .filter(|cuboid| !cuboid.is_empty())
+            .flat_map(|cuboid| [cuboid.x.start, cuboid.x.end()])
+            .collect();
+        boundaries.sort();
+        boundaries.dedup();
+
+        boundaries
+            .windows(2)
+            .map(|slab| {
+                let faces: Vec<Rect<T>> = cuboids
+                    .iter()
+                    .filter(|cuboid| !cuboid.is_empty() && cuboid.x.contains(slab[0]))
+                    .map(|cuboid| Rect::new(cuboid.y.clone(), cuboid.z.clone()))
+                    .collect();
+                Span1D::from_start_end_exclusive(slab[0], slab[1]).len * Rect::union_area(&faces)
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn cuboid(a: (i64, i64, i64), b: (i64, i64, i64)) -> Cuboid<i64> {
+        Cuboid::from_corners(&Coord3D::new(a.0, a.1, a.2), &Coord3D::new(b.0, b.1, b.2))
+    }
+
+    #[test]
+    fn test_subtract_and_union() {
+        let a = cuboid((0, 0, 0), (4, 4, 4));
+        let b = cuboid((2, -1, 1), (3, 2, 9));
+        assert_eq!(a.volume(), 125);
+        assert_eq!(a.intersection(&b), Some(cuboid((2, 0, 1), (3, 2, 4))));
+
+        let pieces = a.subtract(&b);
+        let points: HashSet<Coord3D<i64>> = pieces.iter().flat_map(|p| p.coords()).collect();
+        let expected: HashSet<Coord3D<i64>> = a.coords().filter(|c| !b.contains(c)).collect();
+        assert_eq!(points, expected);
+        assert_eq!(
+            pieces.iter().map(|p| p.volume()).sum::<usize>(),
+            expected.len()
+        );
+
+        let all = vec![a.clone(), b.clone(), cuboid((-2, -2, -2), (0, 0, 0))];
+        let points: HashSet<Coord3D<i64>> = all.iter().flat_map(|c| c.coords()).collect();
+        assert_eq!(Cuboid::union_volume(&all), points.len());
+        assert!(a.contains_cuboid(&cuboid((1, 1, 1), (2, 2, 2))));
+    }
+}
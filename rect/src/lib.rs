@@ -0,0 +1,178 @@
+use std::hash::Hash;
+
+use num::iter::range;
+use num::{Integer, ToPrimitive};
+
+use coord_2d::Coord2D;
+use span_1d::{Span1D, SpanSet};
+
+mod cuboid;
+
+pub use cuboid::Cuboid;
+
+/// An axis-aligned rectangle of lattice points: every (row, col)
+/// with row in `rows` and col in `cols`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rect<T: Integer + Copy + TryFrom<usize>>
+where
+    <T as TryFrom<usize>>::Error: std::fmt::Debug,
+{
+    pub rows: Span1D<T>,
+    pub cols: Span1D<T>,
+}
+
+impl<T: Integer + Copy + Hash + TryFrom<usize> + ToPrimitive> Rect<T>
+where
+    <T as TryFrom<usize>>::Error: std::fmt::Debug,
+{
+    pub fn new(rows: Span1D<T>, cols: Span1D<T>) -> Self {
+        Self { rows, cols }
+    }
+
+    /// The rectangle in which the two points are opposite corners (inclusive)
+    pub fn from_corners(a: &Coord2D<T>, b: &Coord2D<T>) -> Self {
+        Self::new(
+            Span1D::from_start_end_inclusive(a.row, b.row),
+            Span1D::from_start_end_inclusive(a.col, b.col),
+        )
+    }
+
+    pub fn area(&self) -> usize {
+        self.rows.len * self.cols.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty() || self.cols.is_empty()
+    }
+
+    pub fn contains(&self, coord: &Coord2D<T>) -> bool {
+        self.rows.contains(coord.row) && self.cols.contains(coord.col)
+    }
+
+    /// Whether every point of other is in self
+    pub fn contains_rect(&self, other: &Self) -> bool {
+        other.is_empty() || self.intersection(other).as_ref() == Some(other)
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.rows.intersects(&other.rows) && self.cols.intersects(&other.cols)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        Some(Self::new(
+            self.rows.intersection(&other.rows)?,
+            self.cols.intersection(&other.cols)?,
+        ))
+    }
+
+    /// The points of self that are not in other, as at most four
+    /// disjoint rectangles
+    pub fn subtract(&self, other: &Self) -> Vec<Self> {
+        let overlap = match self.intersection(other) {
+            Some(overlap) => overlap,
+            None => return vec![self.clone()],
+        };
+
+        let (above, rest) = self.rows.split_at(overlap.rows.start);
+        let (middle, below) = rest.split_at(overlap.rows.end());
+        let (left, rest) = self.cols.split_at(overlap.cols.start);
+        let (_, right) = rest.split_at(overlap.cols.end());
+
+        [
+            Self::new(above, self.cols.clone()),
+            Self::new(middle.clone(), left),
+            Self::new(middle, right),
+            Self::new(below, self.cols.clone()),
+        ]
+        .into_iter()
+        .filter(|piece| !piece.is_empty())
+        .collect()
+    }
+
+    /// Every point in the rectangle, row by row
+    pub fn coords(&self) -> impl Iterator<Item = Coord2D<T>> + '_ {
+        range(self.rows.start, self.rows.end()).flat_map(move |row| {
+            range(self.cols.start, self.cols.end()).map(move |col| Coord2D::new(row, col))
+        })
+    }
+
+    /// The number of points covered by at least one of rects. Sweeps
+    /// over the rows, measuring the covered columns in each band
+    pub fn union_area(rects: &[Self]) -> usize {
+        let mut boundaries: Vec<T> = rects
+            .iter()
+            .filter(|rect| !rect.is_empty())
+            .flat_map(|rect| [rect.rows.start, rect.rows.end()])
+            .collect();
+        boundaries.sort();
+        boundaries.dedup();
+
+        boundaries
+            .windows(2)
+            .map(|band| {
+                let covered_cols: SpanSet<T> = rects
+                    .iter()
+                    .filter(|rect| !rect.is_empty() && rect.rows.contains(band[0]))
+                    .map(|rect| rect.cols.clone())
+                    .collect();
+                Span1D::from_start_end_exclusive(band[0], band[1]).len * covered_cols.covered_len()
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn rect(top_left: (i32, i32), bottom_right: (i32, i32)) -> Rect<i32> {
+        Rect::from_corners(
+            &Coord2D::new(top_left.0, top_left.1),
+            &Coord2D::new(bottom_right.0, bottom_right.1),
+        )
+    }
+
+    #[test]
+    fn test_intersection_and_contains() {
+        let a = rect((0, 0), (4, 9));
+        let b = rect((3, -2), (7, 2));
+        assert_eq!(a.area(), 50);
+        assert_eq!(a.intersection(&b), Some(rect((3, 0), (4, 2))));
+        assert!(a.contains(&Coord2D::new(4, 9)));
+        assert!(!a.contains(&Coord2D::new(5, 9)));
+        assert!(a.contains_rect(&rect((1, 1), (2, 2))));
+        assert!(!a.contains_rect(&b));
+        assert_eq!(a.intersection(&rect((5, 0), (6, 0))), None);
+    }
+
+    #[test]
+    fn test_subtract() {
+        let a = rect((0, 0), (4, 9));
+        let b = rect((2, 3), (7, 5));
+        let pieces = a.subtract(&b);
+        assert_eq!(pieces.len(), 3);
+        assert_eq!(
+            pieces.iter().map(|piece| piece.area()).sum::<usize>(),
+            a.area() - a.intersection(&b).unwrap().area()
+        );
+
+        let points: HashSet<Coord2D<i32>> = pieces.iter().flat_map(|p| p.coords()).collect();
+        let expected: HashSet<Coord2D<i32>> = a.coords().filter(|c| !b.contains(c)).collect();
+        assert_eq!(points, expected);
+        assert_eq!(a.subtract(&a), vec![]);
+    }
+
+    #[test]
+    fn test_union_area() {
+        let rects = vec![
+            rect((0, 0), (4, 9)),
+            rect((2, 3), (7, 5)),
+            rect((-3, -3), (-1, -1)),
+            rect((3, 3), (3, 4)),
+        ];
+        let points: HashSet<Coord2D<i32>> = rects.iter().flat_map(|r| r.coords()).collect();
+        assert_eq!(Rect::union_area(&rects), points.len());
+    }
+}
@@ -0,0 +1,231 @@
+use num::{Integer, ToPrimitive};
+
+use crate::Span1D;
+
+/// A static index over a collection of (possibly overlapping) spans.
+/// Query results refer to spans by their index in the Vec the tree
+/// was built from; empty spans never match anything
+#[derive(Debug, Clone)]
+pub struct IntervalTree<T: Integer + Copy + TryFrom<usize>>
+where
+    <T as TryFrom<usize>>::Error: std::fmt::Debug,
+{
+    spans: Vec<Span1D<T>>,
+    /// indices of the non-empty spans sorted by start, laid out as an
+    /// implicit tree: the middle element of every sub-slice is the root
+    /// of that subtree
+    by_start: Vec<usize>,
+    /// the largest end in the subtree rooted at each position of by_start
+    max_end: Vec<T>,
+    starts: Vec<T>,
+    ends: Vec<T>,
+}
+
+impl<T: Integer + Copy + TryFrom<usize>> IntervalTree<T>
+where
+    <T as TryFrom<usize>>::Error: std::fmt::Debug,
+    T: ToPrimitive,
+{
+    pub fn new(spans: Vec<Span1D<T>>) -> Self {
+        let mut by_start: Vec<usize> = (0..spans.len())
+            .filter(|idx| !spans[*idx].is_empty())
+            .collect();
+        by_start.sort_by_key(|idx| spans[*idx].start);
+
+        let mut starts: Vec<T> = by_start.iter().map(|idx| spans[*idx].start).collect();
+        let mut ends: Vec<T> = by_start.iter().map(|idx| spans[*idx].end()).collect();
+        starts.sort();
+        ends.sort();
+
+        let mut result = Self {
+            max_end: ends.clone(), // placeholder values, overwritten below
+            spans,
+            by_start,
+            starts,
+            ends,
+        };
+        result.build_max_end(0, result.by_start.len());
+        result
+    }
+
+    fn build_max_end(&mut self, lo: usize, hi: usize) -> Option<T> {
+        if lo >= hi {
+            return None;
+        }
+
+        let mid = lo + (hi - lo) / 2;
+        let mut max_end = self.spans[self.by_start[mid]].end();
+        for child in [self.build_max_end(lo, mid), self.build_max_end(mid + 1, hi)]
+            .into_iter()
+            .flatten()
+        {
+            max_end = std::cmp::max(max_end, child);
+        }
+        self.max_end[mid] = max_end;
+        Some(max_end)
+    }
+
+    pub fn spans(&self) -> &[Span1D<T>] {
+        &self.spans
+    }
+
+    /// The indices of every span containing needle, in ascending order
+    pub fn containing(&self, needle: T) -> Vec<usize> {
+        self.overlapping(&Span1D::new(needle, 1))
+    }
+
+    /// The number of spans containing needle, without listing them
+    pub fn count_containing(&self, needle: T) -> usize {
+        self.starts.partition_point(|start| *start <= needle)
+            - self.ends.partition_point(|end| *end <= needle)
+    }
+
+    /// The indices of every span that intersects query, in ascending order
+    pub fn overlapping(&self, query: &Span1D<T>) -> Vec<usize> {
+        let mut result: Vec<usize> = vec![];
+        if !query.is_empty() {
+            self.search(query, 0, self.by_start.len(), &mut result);
+        }
+        result.sort();
+        result
+    }
+
+    fn search(&self, query: &Span1D<T>, lo: usize, hi: usize, result: &mut Vec<usize>) {
+        if lo >= hi {
+            return;
+        }
+
+        let mid = lo + (hi - lo) / 2;
+        if self.max_end[mid] <= query.start {
+            // nothing in this subtree reaches the query
+            return;
+        }
+
+        self.search(query, lo, mid, result);
+
+        let idx = self.by_start[mid];
+        if self.spans[idx].start >= query.end() {
+            // this span and everything to its right starts too late
+            return;
+        }
+        if self.spans[idx].intersects(query) {
+            result.push(idx);
+        }
+
+        self.search(query, mid + 1, hi, result);
+    }
+
+    /// Sweep the number line, returning each maximal stretch covered by
+    /// at least one span alongside how many spans cover it. Stretches
+    /// that touch and have the same depth come back as one
+    pub fn coverage_depths(&self) -> Vec<(Span1D<T>, usize)> {
+        let mut result: Vec<(Span1D<T>, usize)> = vec![];
+        let (mut start_idx, mut end_idx) = (0usize, 0usize);
+        let mut depth = 0usize;
+        let mut previous: Option<T> = None;
+
+        while end_idx < self.ends.len() {
+            let position = match self.starts.get(start_idx) {
+                Some(start) if *start < self.ends[end_idx] => *start,
+                _ => self.ends[end_idx],
+            };
+
+            if let Some(previous) = previous {
+                if depth > 0 && previous < position {
+                    match result.last_mut() {
+                        Some((last, last_depth))
+                            if *last_depth == depth && last.end() == previous =>
+                        {
+                            *last = Span1D::from_start_end_exclusive(last.start, position);
+                        }
+                        _ => result
+                            .push((Span1D::from_start_end_exclusive(previous, position), depth)),
+                    }
+                }
+            }
+
+            while start_idx < self.starts.len() && self.starts[start_idx] == position {
+                depth += 1;
+                start_idx += 1;
+            }
+            while end_idx < self.ends.len() && self.ends[end_idx] == position {
+                depth -= 1;
+                end_idx += 1;
+            }
+            previous = Some(position);
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans() -> Vec<Span1D<i32>> {
+        vec![
+            Span1D::from_start_end_inclusive(3, 5),
+            Span1D::from_start_end_inclusive(10, 14),
+            Span1D::from_start_end_inclusive(16, 20),
+            Span1D::from_start_end_inclusive(12, 18),
+            Span1D::empty(13),
+            Span1D::from_start_end_inclusive(-4, 30),
+            Span1D::from_start_end_inclusive(19, 19),
+        ]
+    }
+
+    #[test]
+    fn test_stabbing_queries() {
+        let spans = spans();
+        let tree = IntervalTree::new(spans.clone());
+
+        for needle in -6..34 {
+            let brute: Vec<usize> = (0..spans.len())
+                .filter(|idx| spans[*idx].contains(needle))
+                .collect();
+            assert_eq!(tree.containing(needle), brute);
+            assert_eq!(tree.count_containing(needle), brute.len());
+        }
+
+        assert_eq!(
+            tree.overlapping(&Span1D::from_start_end_inclusive(6, 10)),
+            vec![1, 5]
+        );
+        assert_eq!(
+            tree.overlapping(&Span1D::from_start_end_exclusive(-10, -4)),
+            Vec::<usize>::new()
+        );
+    }
+
+    #[test]
+    fn test_coverage_depths() {
+        let tree = IntervalTree::new(spans()[..4].to_vec());
+        assert_eq!(
+            tree.coverage_depths(),
+            vec![
+                (Span1D::from_start_end_inclusive(3, 5), 1),
+                (Span1D::from_start_end_inclusive(10, 11), 1),
+                (Span1D::from_start_end_inclusive(12, 14), 2),
+                (Span1D::from_start_end_inclusive(15, 15), 1),
+                (Span1D::from_start_end_inclusive(16, 18), 2),
+                (Span1D::from_start_end_inclusive(19, 20), 1),
+            ]
+        );
+
+        // abutting spans cover one stretch, not two
+        let abutting = IntervalTree::new(vec![
+            Span1D::<i32>::from_start_end_inclusive(0, 4),
+            Span1D::from_start_end_inclusive(5, 9),
+            Span1D::from_start_end_inclusive(7, 8),
+        ]);
+        assert_eq!(
+            abutting.coverage_depths(),
+            vec![
+                (Span1D::from_start_end_inclusive(0, 6), 1),
+                (Span1D::from_start_end_inclusive(7, 8), 2),
+                (Span1D::from_start_end_inclusive(9, 9), 1),
+            ]
+        );
+    }
+}
@@ -1,6 +1,19 @@
-use num::Integer;
+use num::{CheckedAdd, CheckedSub, Integer, ToPrimitive};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+mod interval_tree;
+mod prefix_sum;
+mod span_set;
+
+pub use interval_tree::IntervalTree;
+pub use prefix_sum::{DifferenceArray, PrefixSum1D};
+pub use span_set::SpanSet;
+
+/// `distance(a, b)` is `b - a` as a usize; panics if b < a
+fn distance<T: Integer + Copy + ToPrimitive>(start: T, end: T) -> usize {
+    (end - start).to_usize().unwrap()
+}
+
+#[derive(Debug, Clone, Eq)]
 pub struct Span1D<T: Integer + Copy + TryFrom<usize>>
 where
     <T as TryFrom<usize>>::Error: std::fmt::Debug,
@@ -9,6 +22,16 @@ where
     pub len: usize,
 }
 
+/// All empty spans are equal, wherever they start
+impl<T: Integer + Copy + TryFrom<usize>> PartialEq for Span1D<T>
+where
+    <T as TryFrom<usize>>::Error: std::fmt::Debug,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && (self.is_empty() || self.start == other.start)
+    }
+}
+
 impl<T: Integer + Copy + TryFrom<usize>> Span1D<T>
 where
     <T as TryFrom<usize>>::Error: std::fmt::Debug,
@@ -20,15 +43,39 @@ where
         Self { start, len }
     }
 
+    pub fn empty(start: T) -> Self {
+        Self { start, len: 0 }
+    }
+
+    /// The span covering both endpoints and everything between them;
+    /// the endpoints may be given in either order
     pub fn from_start_end_inclusive(start: T, end: T) -> Self
     where
-        <T as TryFrom<usize>>::Error: std::fmt::Debug,
-        usize: From<T>,
+        T: ToPrimitive,
     {
-        let len: usize = (end - start).into();
+        let (start, end) = if start <= end {
+            (start, end)
+        } else {
+            (end, start)
+        };
         Self {
             start,
-            len: len + 1,
+            len: distance(start, end) + 1,
+        }
+    }
+
+    /// The span [start, end); if end <= start, the span is empty
+    pub fn from_start_end_exclusive(start: T, end: T) -> Self
+    where
+        T: ToPrimitive,
+    {
+        if end <= start {
+            Self::empty(start)
+        } else {
+            Self {
+                start,
+                len: distance(start, end),
+            }
         }
     }
 
@@ -36,6 +83,10 @@ where
         self.start..self.end()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
     pub fn increment(&mut self) {
         self.len += 1
     }
@@ -44,41 +95,111 @@ where
         self.start + T::try_from(self.len).unwrap()
     }
 
+    /// The largest value in the span, if there is one
+    pub fn last(&self) -> Option<T> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.end() - T::one())
+        }
+    }
+
     pub fn contains(&self, needle: T) -> bool {
         needle >= self.start && needle < self.end()
     }
 
+    /// Empty spans intersect nothing
     pub fn intersects(&self, other: &Self) -> bool {
-        !(self.end() <= other.start || self.start >= other.end())
-    }
-
-    /// WARNING: this function was for day 9 specifically
-    ///  and behaves in a maybe unexpected way for spans
-    /// with length 1 (in that we necessarily can't decrement
-    /// symmetrically)
-    pub fn decrement(&self) -> Self {
-        match self.len {
-            0 => self.clone(),
-            1 => Self {
-                start: self.start,
-                len: 0,
-            },
-            2 => Self {
-                start: self.start + T::one(),
-                len: 0,
-            },
-            _ => Self {
-                start: self.start + T::one(),
-                len: self.len - 2,
-            },
+        !(self.is_empty()
+            || other.is_empty()
+            || self.end() <= other.start
+            || self.start >= other.end())
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self>
+    where
+        T: ToPrimitive,
+    {
+        if self.intersects(other) {
+            Some(Self::from_start_end_exclusive(
+                std::cmp::max(self.start, other.start),
+                std::cmp::min(self.end(), other.end()),
+            ))
+        } else {
+            None
         }
     }
 
+    /// The number of values the two spans have in common
+    pub fn overlap_len(&self, other: &Self) -> usize
+    where
+        T: ToPrimitive,
+    {
+        self.intersection(other).map_or(0, |overlap| overlap.len)
+    }
+
+    /// Drop n values from each end. A span with 2n or fewer values
+    /// becomes an empty span at its middle (rounding down)
+    pub fn shrink(&self, n: usize) -> Self {
+        let trimmed = std::cmp::min(n, self.len / 2);
+        Self {
+            start: self.start + T::try_from(trimmed).unwrap(),
+            len: self.len.saturating_sub(2 * n),
+        }
+    }
+
+    /// Add n values to each end; panics if either end leaves T's range
+    pub fn grow(&self, n: usize) -> Self
+    where
+        T: CheckedAdd + CheckedSub,
+    {
+        let n_t = T::try_from(n).expect("grow amount doesn't fit in T");
+        let start = self
+            .start
+            .checked_sub(&n_t)
+            .expect("grown span starts below T's minimum");
+        self.end()
+            .checked_add(&n_t)
+            .expect("grown span ends above T's maximum");
+        Self {
+            start,
+            len: self.len + 2 * n,
+        }
+    }
+
+    pub fn shift(&self, delta: T) -> Self {
+        Self {
+            start: self.start + delta,
+            len: self.len,
+        }
+    }
+
+    /// Split into the values below `at` and the rest;
+    /// either side may be empty
+    pub fn split_at(&self, at: T) -> (Self, Self)
+    where
+        T: ToPrimitive,
+    {
+        let at = num::clamp(at, self.start, self.end());
+        (
+            Self::from_start_end_exclusive(self.start, at),
+            Self::from_start_end_exclusive(at, self.end()),
+        )
+    }
+
+    /// The span covering both, which must intersect. An empty span
+    /// merges into anything and leaves it unchanged
     pub fn merge(self, other: Self) -> Self
     where
         <T as TryFrom<usize>>::Error: std::fmt::Debug,
-        usize: From<T>,
+        T: ToPrimitive,
     {
+        if self.is_empty() {
+            return other;
+        }
+        if other.is_empty() {
+            return self;
+        }
         if !self.intersects(&other) {
             panic!("cannot merge disjoint spans");
         }
@@ -94,7 +215,7 @@ where
     pub fn melt(mut to_melt: Vec<Self>) -> Vec<Self>
     where
         <T as TryFrom<usize>>::Error: std::fmt::Debug,
-        usize: From<T>,
+        T: ToPrimitive,
     {
         let mut disjoint: Vec<Self> = vec![];
         while let Some(first) = to_melt.pop() {
@@ -138,13 +259,64 @@ mod tests {
             .intersects(&Span1D::<usize>::from_start_end_inclusive(4, 5),));
     }
 
+    #[test]
+    fn test_signed_and_reversed() {
+        let span = Span1D::<i64>::from_start_end_inclusive(3, -2);
+        assert_eq!(span, Span1D::new(-2, 6));
+        assert_eq!(span.last(), Some(3));
+        assert!(span.contains(-2) && !span.contains(4));
+
+        let empty = Span1D::<i64>::from_start_end_exclusive(5, -5);
+        assert!(empty.is_empty());
+        assert_eq!(empty.last(), None);
+        assert!(!empty.intersects(&span));
+        assert!(!Span1D::<usize>::empty(3).intersects(&Span1D::new(0, 10)));
+    }
+
+    #[test]
+    fn test_shrink_and_grow() {
+        let span = Span1D::<usize>::from_start_end_inclusive(2, 6);
+        assert_eq!(span.shrink(1), Span1D::from_start_end_inclusive(3, 5));
+        assert_eq!(span.shrink(2), Span1D::new(4, 1));
+        assert_eq!(span.shrink(10).start, 4);
+        assert_eq!(Span1D::<usize>::new(7, 1).shrink(1).start, 7);
+        assert_eq!(Span1D::<usize>::new(7, 2).shrink(1).start, 8);
+        assert!(span.shrink(10).is_empty());
+        assert_eq!(span.shrink(1).grow(1), span);
+        assert_eq!(
+            Span1D::<i32>::empty(0).grow(3),
+            Span1D::from_start_end_exclusive(-3, 3)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "below T's minimum")]
+    fn test_grow_past_zero() {
+        Span1D::<u8>::new(1, 3).grow(2);
+    }
+
+    #[test]
+    fn test_split_shift_overlap() {
+        let span = Span1D::<i32>::from_start_end_inclusive(-4, 5);
+        assert_eq!(span.split_at(0), (Span1D::new(-4, 4), Span1D::new(0, 6)));
+        assert_eq!(span.split_at(-10), (Span1D::empty(-4), span.clone()));
+        assert_eq!(span.shift(-6), Span1D::from_start_end_inclusive(-10, -1));
+        assert_eq!(span.overlap_len(&span.shift(7)), 3);
+        assert_eq!(span.overlap_len(&span.shift(10)), 0);
+    }
+
     #[test]
     fn test_merge() {
         assert_eq!(
             Span1D::<usize>::from_start_end_inclusive(0, 3)
                 .merge(Span1D::from_start_end_inclusive(2, 5)),
             Span1D::from_start_end_inclusive(0, 5)
-        )
+        );
+        let span = Span1D::<i32>::new(-3, 4);
+        assert_eq!(span.clone().merge(Span1D::empty(100)), span);
+        assert_eq!(Span1D::empty(100).merge(span.clone()), span);
+        assert_eq!(Span1D::<i32>::empty(-5), Span1D::empty(5));
+        assert_ne!(Span1D::<i32>::empty(0), Span1D::new(0, 1));
     }
 
     #[test]
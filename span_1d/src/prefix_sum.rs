@@ -0,0 +1,127 @@
+use std::ops::{Add, Sub};
+
+use num::{Integer, ToPrimitive, Zero};
+
+use crate::{distance, Span1D};
+
+/// Prefix sums over values laid out along a span, answering the sum
+/// over any sub-span in O(1). Queries are clipped to the domain
+#[derive(Debug, Clone)]
+pub struct PrefixSum1D<T: Integer + Copy + TryFrom<usize>, S>
+where
+    <T as TryFrom<usize>>::Error: std::fmt::Debug,
+{
+    domain: Span1D<T>,
+    /// sums[i] is the total of the first i values
+    sums: Vec<S>,
+}
+
+impl<T, S> PrefixSum1D<T, S>
+where
+    T: Integer + Copy + TryFrom<usize> + ToPrimitive,
+    <T as TryFrom<usize>>::Error: std::fmt::Debug,
+    S: Copy + Zero + Add<Output = S> + Sub<Output = S>,
+{
+    /// values[i] sits at start + i
+    pub fn new(start: T, values: &[S]) -> Self {
+        let mut sums: Vec<S> = vec![S::zero()];
+        for value in values.iter() {
+            sums.push(*sums.last().unwrap() + *value);
+        }
+        Self {
+            domain: Span1D::new(start, values.len()),
+            sums,
+        }
+    }
+
+    pub fn domain(&self) -> &Span1D<T> {
+        &self.domain
+    }
+
+    pub fn sum(&self, span: &Span1D<T>) -> S {
+        match self.domain.intersection(span) {
+            Some(overlap) => {
+                let lo = distance(self.domain.start, overlap.start);
+                self.sums[lo + overlap.len] - self.sums[lo]
+            }
+            None => S::zero(),
+        }
+    }
+
+    pub fn total(&self) -> S {
+        *self.sums.last().unwrap()
+    }
+}
+
+/// Accumulates "add delta to every value in this span" updates in O(1)
+/// each, then resolves them all at once. Updates are clipped to the domain
+#[derive(Debug, Clone)]
+pub struct DifferenceArray<T: Integer + Copy + TryFrom<usize>, S>
+where
+    <T as TryFrom<usize>>::Error: std::fmt::Debug,
+{
+    domain: Span1D<T>,
+    /// deltas[i] is how much value i differs from value i - 1
+    deltas: Vec<S>,
+}
+
+impl<T, S> DifferenceArray<T, S>
+where
+    T: Integer + Copy + TryFrom<usize> + ToPrimitive,
+    <T as TryFrom<usize>>::Error: std::fmt::Debug,
+    S: Copy + Zero + Add<Output = S> + Sub<Output = S>,
+{
+    /// Every value in domain starts at zero
+    pub fn new(domain: Span1D<T>) -> Self {
+        Self {
+            deltas: vec![S::zero(); domain.len + 1],
+            domain,
+        }
+    }
+
+    pub fn add(&mut self, span: &Span1D<T>, delta: S) {
+        if let Some(overlap) = self.domain.intersection(span) {
+            let lo = distance(self.domain.start, overlap.start);
+            self.deltas[lo] = self.deltas[lo] + delta;
+            self.deltas[lo + overlap.len] = self.deltas[lo + overlap.len] - delta;
+        }
+    }
+
+    /// The value at each point of the domain, in order
+    pub fn values(&self) -> Vec<S> {
+        let mut running = S::zero();
+        self.deltas[..self.domain.len]
+            .iter()
+            .map(|delta| {
+                running = running + *delta;
+                running
+            })
+            .collect()
+    }
+
+    pub fn into_prefix_sum(self) -> PrefixSum1D<T, S> {
+        PrefixSum1D::new(self.domain.start, &self.values())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_difference_array_and_prefix_sum() {
+        let mut counts: DifferenceArray<i32, i64> =
+            DifferenceArray::new(Span1D::from_start_end_inclusive(-3, 4));
+        counts.add(&Span1D::from_start_end_inclusive(-5, 0), 2);
+        counts.add(&Span1D::from_start_end_inclusive(-1, 2), 10);
+        counts.add(&Span1D::from_start_end_inclusive(4, 9), -1);
+        counts.add(&Span1D::from_start_end_inclusive(7, 9), 100);
+        assert_eq!(counts.values(), vec![2, 2, 12, 12, 10, 10, 0, -1]);
+
+        let sums = counts.into_prefix_sum();
+        assert_eq!(sums.total(), 47);
+        assert_eq!(sums.sum(&Span1D::from_start_end_inclusive(-1, 1)), 34);
+        assert_eq!(sums.sum(&Span1D::from_start_end_inclusive(3, 50)), -1);
+        assert_eq!(sums.sum(&Span1D::from_start_end_inclusive(-50, -4)), 0);
+    }
+}
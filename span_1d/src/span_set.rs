@@ -0,0 +1,218 @@
+use std::collections::BTreeMap;
+
+use num::{Integer, ToPrimitive};
+
+use crate::{distance, Span1D};
+
+/// A set of integers stored as sorted, disjoint, non-adjacent spans.
+/// Spans are keyed by start and hold their (exclusive) end
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SpanSet<T: Integer + Copy + TryFrom<usize>>
+where
+    <T as TryFrom<usize>>::Error: std::fmt::Debug,
+    T: ToPrimitive,
+{
+    spans: BTreeMap<T, T>,
+    covered: usize,
+}
+
+impl<T: Integer + Copy + TryFrom<usize>> SpanSet<T>
+where
+    <T as TryFrom<usize>>::Error: std::fmt::Debug,
+    T: ToPrimitive,
+{
+    pub fn new() -> Self {
+        Self {
+            spans: BTreeMap::new(),
+            covered: 0,
+        }
+    }
+
+    /// The number of integers in the set
+    pub fn covered_len(&self) -> usize {
+        self.covered
+    }
+
+    /// The number of disjoint spans the set is made of
+    pub fn n_spans(&self) -> usize {
+        self.spans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Span1D<T>> + '_ {
+        self.spans
+            .iter()
+            .map(|(start, end)| Span1D::new(*start, distance(*start, *end)))
+    }
+
+    pub fn contains(&self, needle: T) -> bool {
+        match self.spans.range(..=needle).next_back() {
+            Some((_, end)) => needle < *end,
+            None => false,
+        }
+    }
+
+    /// Add every integer in span, merging with any spans it
+    /// overlaps or touches
+    pub fn insert(&mut self, span: Span1D<T>) {
+        if span.len == 0 {
+            return;
+        }
+
+        let mut start = span.start;
+        let mut end = span.end();
+        let touching: Vec<(T, T)> = self
+            .spans
+            .range(..=end)
+            .rev()
+            .take_while(|(_, other_end)| **other_end >= start)
+            .map(|(s, e)| (*s, *e))
+            .collect();
+
+        for (other_start, other_end) in touching {
+            self.spans.remove(&other_start);
+            self.covered -= distance(other_start, other_end);
+            start = std::cmp::min(start, other_start);
+            end = std::cmp::max(end, other_end);
+        }
+
+        self.spans.insert(start, end);
+        self.covered += distance(start, end);
+    }
+
+    /// Remove every integer in span, splitting any span that
+    /// only partially overlaps it
+    pub fn remove(&mut self, span: &Span1D<T>) {
+        if span.len == 0 {
+            return;
+        }
+
+        let (start, end) = (span.start, span.end());
+        let overlapping: Vec<(T, T)> = self
+            .spans
+            .range(..end)
+            .rev()
+            .take_while(|(_, other_end)| **other_end > start)
+            .map(|(s, e)| (*s, *e))
+            .collect();
+
+        for (other_start, other_end) in overlapping {
+            self.spans.remove(&other_start);
+            self.covered -= distance(other_start, other_end);
+            if other_start < start {
+                self.spans.insert(other_start, start);
+                self.covered += distance(other_start, start);
+            }
+            if other_end > end {
+                self.spans.insert(end, other_end);
+                self.covered += distance(end, other_end);
+            }
+        }
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for span in other.iter() {
+            result.insert(span);
+        }
+        result
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for span in other.iter() {
+            result.remove(&span);
+        }
+        result
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut result = Self::new();
+        let mut left = self.spans.iter().peekable();
+        let mut right = other.spans.iter().peekable();
+        while let (Some((l_start, l_end)), Some((r_start, r_end))) = (left.peek(), right.peek()) {
+            let start = std::cmp::max(**l_start, **r_start);
+            let end = std::cmp::min(**l_end, **r_end);
+            if start < end {
+                result.spans.insert(start, end);
+                result.covered += distance(start, end);
+            }
+
+            if l_end < r_end {
+                left.next();
+            } else {
+                right.next();
+            }
+        }
+        result
+    }
+
+    /// Every integer in bound that is not in the set
+    pub fn complement(&self, bound: &Span1D<T>) -> Self {
+        let mut result: Self = Self::from_iter([bound.clone()]);
+        for span in self.iter() {
+            result.remove(&span);
+        }
+        result
+    }
+}
+
+impl<T: Integer + Copy + TryFrom<usize>> FromIterator<Span1D<T>> for SpanSet<T>
+where
+    <T as TryFrom<usize>>::Error: std::fmt::Debug,
+    T: ToPrimitive,
+{
+    fn from_iter<I: IntoIterator<Item = Span1D<T>>>(iter: I) -> Self {
+        let mut result = Self::new();
+        for span in iter {
+            result.insert(span);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(spans: &[(usize, usize)]) -> SpanSet<usize> {
+        spans
+            .iter()
+            .map(|(start, end)| Span1D::from_start_end_inclusive(*start, *end))
+            .collect()
+    }
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut spans = set(&[(3, 5), (10, 14), (16, 20), (12, 18)]);
+        assert_eq!(spans, set(&[(3, 5), (10, 20)]));
+        assert_eq!(spans.covered_len(), 14);
+
+        spans.insert(Span1D::from_start_end_inclusive(6, 6));
+        assert_eq!(spans, set(&[(3, 6), (10, 20)]));
+
+        spans.remove(&Span1D::from_start_end_inclusive(5, 12));
+        assert_eq!(spans, set(&[(3, 4), (13, 20)]));
+        assert_eq!(spans.covered_len(), 10);
+        assert!(spans.contains(4));
+        assert!(!spans.contains(5));
+        assert!(spans.contains(20));
+        assert!(!spans.contains(21));
+    }
+
+    #[test]
+    fn test_set_algebra() {
+        let a = set(&[(0, 9), (20, 29)]);
+        let b = set(&[(5, 24)]);
+        assert_eq!(a.union(&b), set(&[(0, 29)]));
+        assert_eq!(a.intersection(&b), set(&[(5, 9), (20, 24)]));
+        assert_eq!(a.difference(&b), set(&[(0, 4), (25, 29)]));
+        assert_eq!(
+            a.complement(&Span1D::from_start_end_inclusive(0, 39)),
+            set(&[(10, 19), (30, 39)])
+        );
+    }
+}
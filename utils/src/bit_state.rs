@@ -0,0 +1,206 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ParseBitStateError {
+    #[error("expected a state like [.##.], got {0:?}")]
+    MissingBrackets(String),
+    #[error("expected '.' or '#', got {0:?}")]
+    InvalidChar(char),
+}
+
+/// A fixed-length row of on/off bits, like a bank of lights. Short
+/// states live in a single integer; longer ones fall back to a Vec.
+/// Bits past len are always off, so equal states hash the same
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BitState {
+    inner: Bits,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum Bits {
+    Small { bits: u64, len: usize },
+    Medium { bits: u128, len: usize },
+    Large { words: Vec<u64>, len: usize },
+}
+
+impl BitState {
+    /// Every bit off
+    pub fn new(len: usize) -> Self {
+        let inner = if len <= 64 {
+            Bits::Small { bits: 0, len }
+        } else if len <= 128 {
+            Bits::Medium { bits: 0, len }
+        } else {
+            Bits::Large {
+                words: vec![0; len.div_ceil(64)],
+                len,
+            }
+        };
+        BitState { inner }
+    }
+
+    /// Only the bits at indices on, e.g. a button's toggle mask
+    pub fn from_indices(len: usize, indices: &[usize]) -> Self {
+        let mut result = Self::new(len);
+        for idx in indices.iter() {
+            result.set(*idx, true);
+        }
+        result
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.inner {
+            Bits::Small { len, .. } | Bits::Medium { len, .. } | Bits::Large { len, .. } => *len,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn check_idx(&self, idx: usize) {
+        if idx >= self.len() {
+            panic!("bit {} out of range (len={})", idx, self.len());
+        }
+    }
+
+    pub fn get(&self, idx: usize) -> bool {
+        self.check_idx(idx);
+        match &self.inner {
+            Bits::Small { bits, .. } => bits >> idx & 1 == 1,
+            Bits::Medium { bits, .. } => bits >> idx & 1 == 1,
+            Bits::Large { words, .. } => words[idx / 64] >> (idx % 64) & 1 == 1,
+        }
+    }
+
+    pub fn set(&mut self, idx: usize, val: bool) {
+        if self.get(idx) != val {
+            self.flip(idx);
+        }
+    }
+
+    pub fn flip(&mut self, idx: usize) {
+        self.check_idx(idx);
+        match &mut self.inner {
+            Bits::Small { bits, .. } => *bits ^= 1 << idx,
+            Bits::Medium { bits, .. } => *bits ^= 1 << idx,
+            Bits::Large { words, .. } => words[idx / 64] ^= 1 << (idx % 64),
+        }
+    }
+
+    /// Toggle every bit that's on in mask, which must be the same len.
+    /// Same len means same variant, so the match can't fall through
+    pub fn apply(&mut self, mask: &Self) {
+        if self.len() != mask.len() {
+            panic!(
+                "can't apply a mask of len {} to a state of len {}",
+                mask.len(),
+                self.len()
+            );
+        }
+        match (&mut self.inner, &mask.inner) {
+            (Bits::Small { bits, .. }, Bits::Small { bits: other, .. }) => *bits ^= other,
+            (Bits::Medium { bits, .. }, Bits::Medium { bits: other, .. }) => *bits ^= other,
+            (Bits::Large { words, .. }, Bits::Large { words: other, .. }) => {
+                for (word, other) in words.iter_mut().zip(other.iter()) {
+                    *word ^= other;
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// A copy with every bit that's on in mask toggled
+    pub fn toggled(&self, mask: &Self) -> Self {
+        let mut result = self.clone();
+        result.apply(mask);
+        result
+    }
+
+    pub fn count_ones(&self) -> usize {
+        match &self.inner {
+            Bits::Small { bits, .. } => bits.count_ones() as usize,
+            Bits::Medium { bits, .. } => bits.count_ones() as usize,
+            Bits::Large { words, .. } => words.iter().map(|word| word.count_ones() as usize).sum(),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len()).map(|idx| self.get(idx))
+    }
+
+    /// The indices of the bits that are on, ascending
+    pub fn ones(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len()).filter(|idx| self.get(*idx))
+    }
+}
+
+impl FromStr for BitState {
+    type Err = ParseBitStateError;
+
+    /// Parses "[.##.]", where '#' is on
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let inner = s
+            .trim()
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+            .ok_or_else(|| ParseBitStateError::MissingBrackets(s.to_string()))?;
+
+        let mut result = Self::new(inner.chars().count());
+        for (idx, c) in inner.chars().enumerate() {
+            match c {
+                '.' => {}
+                '#' => result.flip(idx),
+                _ => return Err(ParseBitStateError::InvalidChar(c)),
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl Display for BitState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let inner: String = self.iter().map(|on| if on { '#' } else { '.' }).collect();
+        write!(f, "[{}]", inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_display() {
+        let state = BitState::from_str("[.##.]").unwrap();
+        assert_eq!(state, BitState::from_indices(4, &[1, 2]));
+        assert_eq!(state.to_string(), "[.##.]");
+        assert_eq!(state.count_ones(), 2);
+        assert_eq!(
+            BitState::from_str(".##."),
+            Err(ParseBitStateError::MissingBrackets(".##.".to_string()))
+        );
+        assert_eq!(
+            BitState::from_str("[.#x]"),
+            Err(ParseBitStateError::InvalidChar('x'))
+        );
+    }
+
+    #[test]
+    fn test_apply_at_every_size() {
+        for len in [4, 100, 300] {
+            let mut state = BitState::new(len);
+            let mask = BitState::from_indices(len, &[0, len - 1]);
+            state.apply(&mask);
+            assert!(state.get(0) && state.get(len - 1));
+            assert_eq!(state.count_ones(), 2);
+            assert_eq!(state.toggled(&mask), BitState::new(len));
+            assert_eq!(state.ones().collect::<Vec<_>>(), vec![0, len - 1]);
+
+            let round_trip = BitState::from_str(&state.to_string()).unwrap();
+            assert_eq!(round_trip, state);
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use num::{Bounded, Integer};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ComponentsError {
+    #[error("unbalanced brackets in {0:?}")]
+    UnbalancedBrackets(String),
+    #[error("expected {expected} components, found {found}")]
+    WrongComponentCount { expected: usize, found: usize },
+    #[error("expected label {expected:?}, found {found:?}")]
+    UnexpectedLabel { expected: String, found: String },
+}
+
+/// Split something like `(1, 2)` or `<x=1, y=2>` into its components,
+/// one per label, returned in the order of labels. Brackets are
+/// optional. Labelled components can come in any order (each label at
+/// most once), and unlabelled ones fill the remaining slots in order
+pub fn split_components<'a>(s: &'a str, labels: &[&str]) -> Result<Vec<&'a str>, ComponentsError> {
+    let trimmed = s.trim();
+    let inner = match (trimmed.chars().next(), trimmed.chars().last()) {
+        (Some('('), Some(')')) | (Some('<'), Some('>')) => &trimmed[1..(trimmed.len() - 1)],
+        (Some('(' | '<'), _) | (_, Some(')' | '>')) => {
+            return Err(ComponentsError::UnbalancedBrackets(s.to_string()))
+        }
+        _ => trimmed,
+    };
+
+    let components: Vec<&str> = inner.split(',').collect();
+    if components.len() != labels.len() {
+        return Err(ComponentsError::WrongComponentCount {
+            expected: labels.len(),
+            found: components.len(),
+        });
+    }
+
+    let mut slots: Vec<Option<&str>> = vec![None; labels.len()];
+    let mut unlabelled: Vec<&str> = vec![];
+    for component in components {
+        match component.split_once('=') {
+            Some((found, value)) => {
+                let found = found.trim();
+                let slot = labels
+                    .iter()
+                    .position(|label| label.eq_ignore_ascii_case(found))
+                    .filter(|slot| slots[*slot].is_none());
+                match slot {
+                    Some(slot) => slots[slot] = Some(value.trim()),
+                    None => {
+                        return Err(ComponentsError::UnexpectedLabel {
+                            expected: labels
+                                .iter()
+                                .zip(slots.iter())
+                                .filter(|(_, slot)| slot.is_none())
+                                .map(|(label, _)| *label)
+                                .collect::<Vec<_>>()
+                                .join(" or "),
+                            found: found.to_string(),
+                        })
+                    }
+                }
+            }
+            None => unlabelled.push(component.trim()),
+        }
+    }
+
+    let mut unlabelled = unlabelled.into_iter();
+    Ok(slots
+        .into_iter()
+        .map(|slot| slot.or_else(|| unlabelled.next()).unwrap())
+        .collect())
+}
+
+/// |a - b|, for unsigned T too
+pub fn abs_diff<T: Integer + Copy>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+/// v moved by delta, which is -1, 0 or 1, or None if that leaves T's range
+pub fn bounded_step<T: Integer + Bounded + Copy>(v: T, delta: i8) -> Option<T> {
+    match delta {
+        -1 if v > T::min_value() => Some(v - T::one()),
+        1 if v < T::max_value() => Some(v + T::one()),
+        0 => Some(v),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_components() {
+        let labels = ["x", "y", "z"];
+        assert_eq!(
+            split_components(" ( 1, 2 ,3)", &labels),
+            Ok(vec!["1", "2", "3"])
+        );
+        assert_eq!(
+            split_components("<z=3, X=1, y=2>", &labels),
+            Ok(vec!["1", "2", "3"])
+        );
+        assert_eq!(
+            split_components("z=3,1,2", &labels),
+            Ok(vec!["1", "2", "3"])
+        );
+        assert_eq!(
+            split_components("x=1,x=2,3", &labels),
+            Err(ComponentsError::UnexpectedLabel {
+                expected: "y or z".to_string(),
+                found: "x".to_string()
+            })
+        );
+        assert_eq!(
+            split_components("1,2", &labels),
+            Err(ComponentsError::WrongComponentCount {
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            split_components("1,2,3>", &labels),
+            Err(ComponentsError::UnbalancedBrackets("1,2,3>".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_abs_diff() {
+        assert_eq!(abs_diff(3u8, 5), 2);
+        assert_eq!(abs_diff(5u8, 3), 2);
+        assert_eq!(abs_diff(-4i32, 3), 7);
+    }
+
+    #[test]
+    fn test_bounded_step() {
+        assert_eq!(bounded_step(0u8, -1), None);
+        assert_eq!(bounded_step(0u8, 1), Some(1));
+        assert_eq!(bounded_step(i8::MAX, 1), None);
+        assert_eq!(bounded_step(-3i8, 0), Some(-3));
+        assert_eq!(bounded_step(5, 2), None);
+    }
+}
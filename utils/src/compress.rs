@@ -0,0 +1,116 @@
+use num::{CheckedAdd, CheckedSub, Integer, ToPrimitive};
+
+/// Maps a handful of (possibly huge or far apart) axis values onto dense
+/// indices 0..len. Compressed cell i stands for every real value from
+/// the i-th kept value up to (but not including) the next one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoordinateCompressor<T: Integer + Copy> {
+    values: Vec<T>,
+}
+
+impl<T: Integer + Copy + ToPrimitive> CoordinateCompressor<T> {
+    pub fn new(values: impl IntoIterator<Item = T>) -> Self {
+        let mut values: Vec<T> = values.into_iter().collect();
+        values.sort();
+        values.dedup();
+        Self { values }
+    }
+
+    /// Also keep each value's neighbors, so that every original value
+    /// gets a cell of width one, and the gaps between values get cells
+    /// of their own rather than being merged into a neighbor
+    pub fn with_neighbors(values: impl IntoIterator<Item = T>) -> Self
+    where
+        T: CheckedAdd + CheckedSub,
+    {
+        Self::new(values.into_iter().flat_map(|value| {
+            [
+                value.checked_sub(&T::one()),
+                Some(value),
+                value.checked_add(&T::one()),
+            ]
+            .into_iter()
+            .flatten()
+        }))
+    }
+
+    /// The number of compressed cells
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// The kept values, sorted; values()[i] is the start of cell i
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// The index of the cell starting at value, if value was kept
+    pub fn compress(&self, value: T) -> Option<usize> {
+        self.values.binary_search(&value).ok()
+    }
+
+    /// The index of the cell containing value, if any cell does.
+    /// The last cell only contains its own value
+    pub fn cell_of(&self, value: T) -> Option<usize> {
+        match self.values.binary_search(&value) {
+            Ok(idx) => Some(idx),
+            Err(0) => None,
+            Err(idx) if idx == self.values.len() => None,
+            Err(idx) => Some(idx - 1),
+        }
+    }
+
+    /// The value at the start of the cell at idx
+    pub fn decompress(&self, idx: usize) -> Option<T> {
+        self.values.get(idx).copied()
+    }
+
+    /// How many real values the cell at idx stands for
+    pub fn cell_width(&self, idx: usize) -> usize {
+        match (self.values.get(idx), self.values.get(idx + 1)) {
+            (Some(start), Some(end)) => (*end - *start).to_usize().unwrap(),
+            (Some(_), None) => 1,
+            (None, _) => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compress() {
+        let compressor = CoordinateCompressor::new([100, 7, 100_000, 7, 42]);
+        assert_eq!(compressor.values(), &[7, 42, 100, 100_000]);
+        assert_eq!(compressor.compress(100), Some(2));
+        assert_eq!(compressor.compress(99), None);
+        assert_eq!(compressor.cell_of(99), Some(1));
+        assert_eq!(compressor.cell_of(100_001), None);
+        assert_eq!(compressor.decompress(3), Some(100_000));
+        assert_eq!(
+            (0..compressor.len())
+                .map(|idx| compressor.cell_width(idx))
+                .collect::<Vec<usize>>(),
+            vec![35, 58, 99_900, 1]
+        );
+    }
+
+    #[test]
+    fn test_with_neighbors() {
+        let compressor = CoordinateCompressor::<u32>::with_neighbors([0, 5, 6, 20]);
+        assert_eq!(compressor.values(), &[0, 1, 4, 5, 6, 7, 19, 20, 21]);
+        assert_eq!(compressor.cell_width(1), 3);
+        assert_eq!(compressor.cell_width(3), 1);
+        assert_eq!(
+            (0..compressor.len())
+                .map(|idx| compressor.cell_width(idx))
+                .sum::<usize>(),
+            22
+        );
+    }
+}
@@ -0,0 +1,403 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use num::{Integer, ToPrimitive};
+use span_1d::Span1D;
+
+/// A state machine fed one digit at a time, most significant first.
+/// Numbers never have leading zeros, and 0 is the single digit 0
+pub trait DigitAutomaton {
+    type State: Clone + Eq + Hash;
+
+    /// At least 2
+    fn base(&self) -> u32 {
+        10
+    }
+
+    /// The state before any digits of an n_digits long number are read
+    fn start(&self, n_digits: usize) -> Self::State;
+
+    /// The state after reading digit, or None if no number with this
+    /// prefix can be accepted
+    fn step(&self, state: &Self::State, digit: u32) -> Option<Self::State>;
+
+    fn accepts(&self, state: &Self::State) -> bool;
+}
+
+/// What we know about the accepted numbers below some point in the
+/// search, as values of the remaining digits only
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Summary {
+    count: u128,
+    /// None once it's too big for a u128
+    sum: Option<u128>,
+    max: Option<u128>,
+    min: Option<u128>,
+}
+
+impl Summary {
+    const EMPTY: Summary = Summary {
+        count: 0,
+        sum: Some(0),
+        max: None,
+        min: None,
+    };
+
+    const ACCEPTED: Summary = Summary {
+        count: 1,
+        sum: Some(0),
+        max: Some(0),
+        min: Some(0),
+    };
+
+    /// Prefix every number with a digit worth offset
+    fn shifted(&self, offset: u128) -> Self {
+        Summary {
+            count: self.count,
+            sum: self
+                .sum
+                .and_then(|sum| sum.checked_add(offset.checked_mul(self.count)?)),
+            max: self.max.map(|max| max + offset),
+            min: self.min.map(|min| min + offset),
+        }
+    }
+
+    fn merge(&self, other: &Self) -> Self {
+        Summary {
+            count: self.count + other.count,
+            sum: self.sum.and_then(|sum| sum.checked_add(other.sum?)),
+            max: self.max.max(other.max),
+            min: match (self.min, other.min) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            },
+        }
+    }
+}
+
+/// The digits of n, zero padded to n_digits
+fn to_digits(mut n: u128, base: u32, n_digits: usize) -> Vec<u32> {
+    let mut result = vec![0; n_digits];
+    for digit in result.iter_mut().rev() {
+        *digit = (n % base as u128) as u32;
+        n /= base as u128;
+    }
+    result
+}
+
+/// Answers "which numbers in this range does the automaton accept"
+/// questions without visiting them one by one. Digits are chosen
+/// between the range's bounds, and once a prefix is strictly inside
+/// both of them, the rest of the answer depends only on the position
+/// and state, so it gets memoized
+pub struct DigitDp<A: DigitAutomaton> {
+    automaton: A,
+}
+
+struct Search<'a, A: DigitAutomaton> {
+    automaton: &'a A,
+    lower: Vec<u32>,
+    upper: Vec<u32>,
+    place_values: Vec<u128>,
+    memo: HashMap<(usize, A::State), Summary>,
+}
+
+impl<A: DigitAutomaton> Search<'_, A> {
+    fn go(&mut self, pos: usize, state: A::State, tight_lower: bool, tight_upper: bool) -> Summary {
+        if pos == self.lower.len() {
+            return if self.automaton.accepts(&state) {
+                Summary::ACCEPTED
+            } else {
+                Summary::EMPTY
+            };
+        }
+
+        let loose = !tight_lower && !tight_upper;
+        if loose {
+            if let Some(summary) = self.memo.get(&(pos, state.clone())) {
+                return *summary;
+            }
+        }
+
+        let smallest = if tight_lower { self.lower[pos] } else { 0 };
+        let largest = if tight_upper {
+            self.upper[pos]
+        } else {
+            self.automaton.base() - 1
+        };
+
+        let mut result = Summary::EMPTY;
+        for digit in smallest..=largest {
+            if let Some(next) = self.automaton.step(&state, digit) {
+                let rest = self.go(
+                    pos + 1,
+                    next,
+                    tight_lower && digit == smallest,
+                    tight_upper && digit == largest,
+                );
+                result = result.merge(&rest.shifted(digit as u128 * self.place_values[pos]));
+            }
+        }
+
+        if loose {
+            self.memo.insert((pos, state), result);
+        }
+        result
+    }
+}
+
+impl<A: DigitAutomaton> DigitDp<A> {
+    pub fn new(automaton: A) -> Self {
+        assert!(automaton.base() >= 2, "digits need a base of at least 2");
+        DigitDp { automaton }
+    }
+
+    fn summarize<T>(&self, span: &Span1D<T>) -> Summary
+    where
+        T: Integer + Copy + TryFrom<usize> + ToPrimitive,
+        <T as TryFrom<usize>>::Error: std::fmt::Debug,
+    {
+        let (lo, hi) = match span.last() {
+            Some(last) => (
+                span.start.to_u128().expect("span must be non-negative"),
+                last.to_u128().unwrap(),
+            ),
+            None => return Summary::EMPTY,
+        };
+
+        let base = self.automaton.base() as u128;
+        let mut result = Summary::EMPTY;
+        let mut smallest_with_len = 0u128;
+        for n_digits in 1.. {
+            // every u128 has at most n_digits digits once base^n_digits
+            // is too big for one
+            let largest_with_len = base
+                .checked_pow(n_digits as u32)
+                .map_or(u128::MAX, |power| power - 1);
+            if smallest_with_len > hi {
+                break;
+            }
+            if largest_with_len >= lo {
+                let lower = lo.max(smallest_with_len);
+                let upper = hi.min(largest_with_len);
+                let mut search = Search {
+                    automaton: &self.automaton,
+                    lower: to_digits(lower, base as u32, n_digits),
+                    upper: to_digits(upper, base as u32, n_digits),
+                    place_values: (0..n_digits)
+                        .rev()
+                        .map(|exp| base.pow(exp as u32))
+                        .collect(),
+                    memo: HashMap::new(),
+                };
+                result = result.merge(&search.go(0, self.automaton.start(n_digits), true, true));
+            }
+            if largest_with_len == u128::MAX {
+                break;
+            }
+            smallest_with_len = largest_with_len + 1;
+        }
+        result
+    }
+
+    /// How many numbers in span are accepted
+    pub fn count<T>(&self, span: &Span1D<T>) -> u128
+    where
+        T: Integer + Copy + TryFrom<usize> + ToPrimitive,
+        <T as TryFrom<usize>>::Error: std::fmt::Debug,
+    {
+        self.summarize(span).count
+    }
+
+    /// The sum of the accepted numbers in span; panics if that doesn't
+    /// fit in a u128
+    pub fn sum<T>(&self, span: &Span1D<T>) -> u128
+    where
+        T: Integer + Copy + TryFrom<usize> + ToPrimitive,
+        <T as TryFrom<usize>>::Error: std::fmt::Debug,
+    {
+        self.summarize(span)
+            .sum
+            .expect("sum of accepted numbers overflows u128")
+    }
+
+    /// The largest accepted number in span, if there is one
+    pub fn max<T>(&self, span: &Span1D<T>) -> Option<u128>
+    where
+        T: Integer + Copy + TryFrom<usize> + ToPrimitive,
+        <T as TryFrom<usize>>::Error: std::fmt::Debug,
+    {
+        self.summarize(span).max
+    }
+
+    /// The smallest accepted number in span, if there is one
+    pub fn min<T>(&self, span: &Span1D<T>) -> Option<u128>
+    where
+        T: Integer + Copy + TryFrom<usize> + ToPrimitive,
+        <T as TryFrom<usize>>::Error: std::fmt::Debug,
+    {
+        self.summarize(span).min
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Digits add up to a multiple of 7
+    struct DigitSum;
+
+    impl DigitAutomaton for DigitSum {
+        type State = u32;
+
+        fn start(&self, _: usize) -> u32 {
+            0
+        }
+
+        fn step(&self, state: &u32, digit: u32) -> Option<u32> {
+            Some((state + digit) % 7)
+        }
+
+        fn accepts(&self, state: &u32) -> bool {
+            *state == 0
+        }
+    }
+
+    /// The first half of the digits is repeated in the second half,
+    /// like day 2's invalid ids. The state is (n_digits, pos, first half)
+    struct Doubled;
+
+    impl DigitAutomaton for Doubled {
+        type State = (usize, usize, Vec<u32>);
+
+        fn start(&self, n_digits: usize) -> Self::State {
+            (n_digits, 0, vec![])
+        }
+
+        fn step(&self, state: &Self::State, digit: u32) -> Option<Self::State> {
+            let (n_digits, pos, half) = state;
+            if n_digits % 2 == 1 {
+                None
+            } else if *pos < n_digits / 2 {
+                let mut half = half.clone();
+                half.push(digit);
+                Some((*n_digits, pos + 1, half))
+            } else {
+                (half[pos - n_digits / 2] == digit).then(|| (*n_digits, pos + 1, half.clone()))
+            }
+        }
+
+        fn accepts(&self, state: &Self::State) -> bool {
+            state.1 == state.0
+        }
+    }
+
+    /// No two adjacent binary digits are both 1
+    struct Fibbinary;
+
+    impl DigitAutomaton for Fibbinary {
+        type State = bool;
+
+        fn base(&self) -> u32 {
+            2
+        }
+
+        fn start(&self, _: usize) -> bool {
+            false
+        }
+
+        fn step(&self, last_was_one: &bool, digit: u32) -> Option<bool> {
+            match (last_was_one, digit) {
+                (true, 1) => None,
+                _ => Some(digit == 1),
+            }
+        }
+
+        fn accepts(&self, _: &bool) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let dp = DigitDp::new(DigitSum);
+        let digit_sum = |n: u64| {
+            n.to_string()
+                .bytes()
+                .map(|b| (b - b'0') as u64)
+                .sum::<u64>()
+        };
+        for (start, last) in [
+            (0u64, 0u64),
+            (0, 5000),
+            (13, 987),
+            (1000, 1000),
+            (98, 10_203),
+        ] {
+            let span = Span1D::from_start_end_inclusive(start, last);
+            let brute: Vec<u128> = (start..=last)
+                .filter(|n| digit_sum(*n) % 7 == 0)
+                .map(|n| n as u128)
+                .collect();
+            assert_eq!(dp.count(&span), brute.len() as u128);
+            assert_eq!(dp.sum(&span), brute.iter().sum());
+            assert_eq!(dp.max(&span), brute.iter().max().copied());
+            assert_eq!(dp.min(&span), brute.iter().min().copied());
+        }
+
+        let dp = DigitDp::new(Fibbinary);
+        let span = Span1D::from_start_end_inclusive(0usize, 1023);
+        assert_eq!(dp.count(&span), 144);
+        assert_eq!(dp.max(&span), Some(0b1010101010));
+    }
+
+    #[test]
+    fn test_huge_range() {
+        let dp = DigitDp::new(Doubled);
+        let span = Span1D::from_start_end_inclusive(1_188_511_880u64, 1_188_511_890);
+        assert_eq!(dp.sum(&span), 1_188_511_885);
+        let span = Span1D::from_start_end_inclusive(1u64, 99_999_999);
+        assert_eq!(dp.count(&span), 9_999);
+        assert_eq!(dp.min(&span), Some(11));
+        assert_eq!(dp.max(&span), Some(99_999_999));
+    }
+
+    #[test]
+    fn test_top_of_u128() {
+        let dp = DigitDp::new(DigitSum);
+        let span = Span1D::from_start_end_inclusive(10u128.pow(38), 10u128.pow(38) + 20);
+        assert_eq!(dp.min(&span), Some(10u128.pow(38) + 6));
+        assert_eq!(dp.count(&span), 2);
+
+        let dp = DigitDp::new(Fibbinary);
+        let span = Span1D::from_start_end_inclusive(1u128 << 127, (1u128 << 127) + 3);
+        assert_eq!(dp.count(&span), 3);
+        assert_eq!(dp.max(&span), Some((1u128 << 127) + 2));
+    }
+
+    #[test]
+    #[should_panic(expected = "base of at least 2")]
+    fn test_unary() {
+        struct Unary;
+
+        impl DigitAutomaton for Unary {
+            type State = ();
+
+            fn base(&self) -> u32 {
+                1
+            }
+
+            fn start(&self, _: usize) {}
+
+            fn step(&self, _: &(), _: u32) -> Option<()> {
+                Some(())
+            }
+
+            fn accepts(&self, _: &()) -> bool {
+                true
+            }
+        }
+
+        DigitDp::new(Unary);
+    }
+}
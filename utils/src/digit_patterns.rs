@@ -0,0 +1,160 @@
+use std::ops::RangeInclusive;
+
+use crate::factorize;
+
+/// The number of decimal digits in n (1 for 0)
+pub fn n_digits(n: u64) -> u32 {
+    n.checked_ilog10().unwrap_or(0) + 1
+}
+
+/// (count, sum) of the numbers in range with exactly n_digits digits
+/// that are some block of digits written `repeats` times, like 123123.
+/// Those are exactly block * (1 + 10^len + 10^2len + ...) for a block
+/// of len = n_digits / repeats digits, so we never need to look at
+/// the numbers themselves
+fn repeated_with_n_digits(range: &RangeInclusive<u64>, n_digits: u32, repeats: u32) -> (u64, u128) {
+    if repeats == 0 || !n_digits.is_multiple_of(repeats) {
+        return (0, 0);
+    }
+
+    let block_len = n_digits / repeats;
+    let multiplier: u128 = (0..repeats).map(|idx| 10u128.pow(idx * block_len)).sum();
+    let (lo, hi) = (*range.start() as u128, *range.end() as u128);
+
+    let smallest_block = std::cmp::max(10u128.pow(block_len - 1), lo.div_ceil(multiplier));
+    let largest_block = std::cmp::min(10u128.pow(block_len) - 1, hi / multiplier);
+    if smallest_block > largest_block {
+        return (0, 0);
+    }
+
+    let count = largest_block - smallest_block + 1;
+    let block_sum = (smallest_block + largest_block) * count / 2;
+    (count as u64, block_sum * multiplier)
+}
+
+fn digit_lengths(range: &RangeInclusive<u64>) -> RangeInclusive<u32> {
+    n_digits(*range.start())..=n_digits(*range.end())
+}
+
+/// (count, sum) of the numbers in range whose digits are one block
+/// repeated `repeats` times
+fn repeated(range: &RangeInclusive<u64>, repeats: u32) -> (u64, u128) {
+    if range.is_empty() {
+        return (0, 0);
+    }
+    digit_lengths(range)
+        .map(|n_digits| repeated_with_n_digits(range, n_digits, repeats))
+        .fold((0, 0), |acc, (count, sum)| (acc.0 + count, acc.1 + sum))
+}
+
+/// (count, sum) of the numbers in range whose digits are a block
+/// repeated at least twice. Repeating k times implies repeating p times
+/// for any prime p dividing k, so we add up the prime repeat counts and
+/// fix the double counting by inclusion-exclusion over their products
+fn periodic(range: &RangeInclusive<u64>) -> (u64, u128) {
+    if range.is_empty() {
+        return (0, 0);
+    }
+
+    let (mut count, mut sum) = (0i128, 0i128);
+    for n_digits in digit_lengths(range) {
+        let primes: Vec<u32> = factorize(n_digits as u64)
+            .into_iter()
+            .map(|(prime, _)| prime as u32)
+            .collect();
+        for subset in 1usize..(1 << primes.len()) {
+            let repeats: u32 = (0..primes.len())
+                .filter(|idx| subset >> idx & 1 == 1)
+                .map(|idx| primes[idx])
+                .product();
+            let sign = if subset.count_ones() % 2 == 1 { 1 } else { -1 };
+            let (subset_count, subset_sum) = repeated_with_n_digits(range, n_digits, repeats);
+            count += sign * subset_count as i128;
+            sum += sign * subset_sum as i128;
+        }
+    }
+    (count as u64, sum as u128)
+}
+
+/// How many numbers in range are a block of digits written exactly
+/// `repeats` times, like 6464 or 777 (for 2 and 3 repeats)
+pub fn count_repeated_blocks(range: RangeInclusive<u64>, repeats: u32) -> u64 {
+    repeated(&range, repeats).0
+}
+
+/// The sum of the numbers in range that are a block of digits written
+/// exactly `repeats` times
+pub fn sum_repeated_blocks(range: RangeInclusive<u64>, repeats: u32) -> u128 {
+    repeated(&range, repeats).1
+}
+
+/// How many numbers in range are a block of digits written two or
+/// more times
+pub fn count_periodic(range: RangeInclusive<u64>) -> u64 {
+    periodic(&range).0
+}
+
+/// The sum of the numbers in range that are a block of digits written
+/// two or more times
+pub fn sum_periodic(range: RangeInclusive<u64>) -> u128 {
+    periodic(&range).1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_periodic(n: u64, repeats: Option<usize>) -> bool {
+        let s = n.to_string();
+        let fits = |repeats: usize| {
+            s.len().is_multiple_of(repeats) && s[..(s.len() / repeats)].repeat(repeats) == s
+        };
+        match repeats {
+            Some(repeats) => fits(repeats),
+            None => (2..=s.len()).any(fits),
+        }
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        for range in [
+            1..=100_000u64,
+            95..=115,
+            998..=1012,
+            1_188_511_880..=1_188_511_890,
+        ] {
+            for repeats in 1..=6u32 {
+                let brute: Vec<u64> = range
+                    .clone()
+                    .filter(|n| is_periodic(*n, Some(repeats as usize)))
+                    .collect();
+                assert_eq!(
+                    count_repeated_blocks(range.clone(), repeats),
+                    brute.len() as u64
+                );
+                assert_eq!(
+                    sum_repeated_blocks(range.clone(), repeats),
+                    brute.iter().map(|n| *n as u128).sum::<u128>()
+                );
+            }
+
+            let brute: Vec<u64> = range.clone().filter(|n| is_periodic(*n, None)).collect();
+            assert_eq!(count_periodic(range.clone()), brute.len() as u64);
+            assert_eq!(
+                sum_periodic(range.clone()),
+                brute.iter().map(|n| *n as u128).sum::<u128>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_huge_ranges() {
+        // every 2-block number up to 9 digit blocks, then 10 digit blocks
+        // from 1000000000 up to where they'd overflow
+        assert_eq!(count_repeated_blocks(0..=u64::MAX, 2), 1_844_674_407);
+        assert_eq!(sum_periodic(95..=115), 99 + 111);
+        assert_eq!(count_periodic(1..=9), 0);
+        assert_eq!(n_digits(0), 1);
+        assert_eq!(n_digits(u64::MAX), 20);
+    }
+}
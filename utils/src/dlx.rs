@@ -0,0 +1,291 @@
+/// Counters collected over every search run on an ExactCover
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchStats {
+    /// the number of times we chose a row to try
+    pub nodes: usize,
+    /// the number of links rewired while covering columns
+    pub updates: usize,
+    pub solutions: usize,
+}
+
+/// Knuth's Algorithm X with Dancing Links. Every primary column must be
+/// covered by exactly one chosen row; every secondary column may be
+/// covered at most once. Columns 0..n_primary are primary and the next
+/// n_secondary are secondary; rows are numbered in the order added
+#[derive(Debug, Clone)]
+pub struct ExactCover {
+    n_columns: usize,
+    /// node 0 is the root, nodes 1..=n_columns are column headers, the
+    /// rest are the 1s of the matrix
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    column: Vec<usize>,
+    row: Vec<usize>,
+    size: Vec<usize>,
+    n_rows: usize,
+    stats: SearchStats,
+}
+
+impl ExactCover {
+    pub fn new(n_primary: usize, n_secondary: usize) -> Self {
+        let n_columns = n_primary + n_secondary;
+        let n_headers = n_columns + 1;
+        let mut left: Vec<usize> = (0..n_headers).map(|idx| idx.wrapping_sub(1)).collect();
+        let mut right: Vec<usize> = (1..=n_headers).collect();
+
+        // primary headers form a ring with the root; secondary ones are
+        // linked only to themselves so the search never picks them
+        left[0] = n_primary;
+        right[n_primary] = 0;
+        for header in (n_primary + 1)..n_headers {
+            left[header] = header;
+            right[header] = header;
+        }
+
+        Self {
+            n_columns,
+            left,
+            right,
+            up: (0..n_headers).collect(),
+            down: (0..n_headers).collect(),
+            column: (0..n_headers).collect(),
+            row: vec![usize::MAX; n_headers],
+            size: vec![0; n_headers],
+            n_rows: 0,
+            stats: SearchStats::default(),
+        }
+    }
+
+    pub fn n_rows(&self) -> usize {
+        self.n_rows
+    }
+
+    pub fn stats(&self) -> &SearchStats {
+        &self.stats
+    }
+
+    /// Add a row with 1s in the given columns, returning its index
+    pub fn add_row(&mut self, columns: &[usize]) -> usize {
+        let mut columns = columns.to_vec();
+        columns.sort();
+        columns.dedup();
+
+        let row_idx = self.n_rows;
+        self.n_rows += 1;
+        let first = self.left.len();
+        for (offset, col) in columns.iter().enumerate() {
+            if *col >= self.n_columns {
+                panic!("column {} out of range (n_columns={})", col, self.n_columns);
+            }
+            let (header, node) = (col + 1, first + offset);
+            self.left.push(if offset == 0 { node } else { node - 1 });
+            self.right.push(first);
+            if offset > 0 {
+                self.right[node - 1] = node;
+                self.left[first] = node;
+            }
+            self.up.push(self.up[header]);
+            self.down.push(header);
+            let above = self.up[header];
+            self.down[above] = node;
+            self.up[header] = node;
+            self.column.push(header);
+            self.row.push(row_idx);
+            self.size[header] += 1;
+        }
+        row_idx
+    }
+
+    fn cover(&mut self, header: usize) {
+        let (l, r) = (self.left[header], self.right[header]);
+        self.right[l] = r;
+        self.left[r] = l;
+
+        let mut row_node = self.down[header];
+        while row_node != header {
+            let mut node = self.right[row_node];
+            while node != row_node {
+                let (u, d) = (self.up[node], self.down[node]);
+                self.down[u] = d;
+                self.up[d] = u;
+                self.size[self.column[node]] -= 1;
+                self.stats.updates += 1;
+                node = self.right[node];
+            }
+            row_node = self.down[row_node];
+        }
+    }
+
+    fn uncover(&mut self, header: usize) {
+        let mut row_node = self.up[header];
+        while row_node != header {
+            let mut node = self.left[row_node];
+            while node != row_node {
+                let (u, d) = (self.up[node], self.down[node]);
+                self.down[u] = node;
+                self.up[d] = node;
+                self.size[self.column[node]] += 1;
+                node = self.left[node];
+            }
+            row_node = self.up[row_node];
+        }
+
+        let (l, r) = (self.left[header], self.right[header]);
+        self.right[l] = header;
+        self.left[r] = header;
+    }
+
+    /// Call on_solution with the row indices of each solution, until it
+    /// returns false or limit solutions have been found. Returns the
+    /// number of solutions found
+    pub fn for_each_solution(
+        &mut self,
+        limit: Option<usize>,
+        mut on_solution: impl FnMut(&[usize]) -> bool,
+    ) -> usize {
+        let mut chosen: Vec<usize> = vec![];
+        let mut n_found = 0;
+        let limit = limit.unwrap_or(usize::MAX);
+        if limit > 0 {
+            self.search(&mut chosen, &mut n_found, limit, &mut on_solution);
+        }
+        n_found
+    }
+
+    /// Returns false once we should stop searching
+    fn search(
+        &mut self,
+        chosen: &mut Vec<usize>,
+        n_found: &mut usize,
+        limit: usize,
+        on_solution: &mut impl FnMut(&[usize]) -> bool,
+    ) -> bool {
+        if self.right[0] == 0 {
+            *n_found += 1;
+            self.stats.solutions += 1;
+            let rows: Vec<usize> = chosen.iter().map(|node| self.row[*node]).collect();
+            return on_solution(&rows) && *n_found < limit;
+        }
+
+        // branch on the column with the fewest options
+        let mut header = self.right[0];
+        let mut best = header;
+        while header != 0 {
+            if self.size[header] < self.size[best] {
+                best = header;
+            }
+            header = self.right[header];
+        }
+        if self.size[best] == 0 {
+            return true;
+        }
+
+        self.cover(best);
+        let mut keep_going = true;
+        let mut row_node = self.down[best];
+        while keep_going && row_node != best {
+            self.stats.nodes += 1;
+            chosen.push(row_node);
+            let mut node = self.right[row_node];
+            while node != row_node {
+                self.cover(self.column[node]);
+                node = self.right[node];
+            }
+
+            keep_going = self.search(chosen, n_found, limit, on_solution);
+
+            let mut node = self.left[row_node];
+            while node != row_node {
+                self.uncover(self.column[node]);
+                node = self.left[node];
+            }
+            chosen.pop();
+            row_node = self.down[row_node];
+        }
+        self.uncover(best);
+        keep_going
+    }
+
+    /// Up to limit solutions, each a sorted list of row indices
+    pub fn solutions(&mut self, limit: Option<usize>) -> Vec<Vec<usize>> {
+        let mut result: Vec<Vec<usize>> = vec![];
+        self.for_each_solution(limit, |rows| {
+            let mut rows = rows.to_vec();
+            rows.sort();
+            result.push(rows);
+            true
+        });
+        result
+    }
+
+    pub fn first_solution(&mut self) -> Option<Vec<usize>> {
+        self.solutions(Some(1)).into_iter().next()
+    }
+
+    pub fn count_solutions(&mut self, limit: Option<usize>) -> usize {
+        self.for_each_solution(limit, |_| true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_knuth_example() {
+        // the example from Knuth's "Dancing Links" paper
+        let mut problem = ExactCover::new(7, 0);
+        for row in [
+            vec![2, 4, 5],
+            vec![0, 3, 6],
+            vec![1, 2, 5],
+            vec![0, 3],
+            vec![1, 6],
+            vec![3, 4, 6],
+        ] {
+            problem.add_row(&row);
+        }
+        assert_eq!(problem.solutions(None), vec![vec![0, 3, 4]]);
+        assert_eq!(problem.stats().solutions, 1);
+        assert!(problem.stats().nodes > 0);
+    }
+
+    #[test]
+    fn test_secondary_columns_and_limits() {
+        // tile a 2x3 board with dominoes: 3 solutions
+        let cell = |row: usize, col: usize| row * 3 + col;
+        let mut problem = ExactCover::new(6, 0);
+        for row in 0..2 {
+            for col in 0..3 {
+                if col + 1 < 3 {
+                    problem.add_row(&[cell(row, col), cell(row, col + 1)]);
+                }
+                if row + 1 < 2 {
+                    problem.add_row(&[cell(row, col), cell(row + 1, col)]);
+                }
+            }
+        }
+        assert_eq!(problem.count_solutions(None), 3);
+        assert_eq!(problem.count_solutions(Some(2)), 2);
+        assert_eq!(problem.first_solution().map(|rows| rows.len()), Some(3));
+
+        // two rooks on a 2x2 board, one per row and column, may not
+        // share a square colour (secondary): every placement does
+        let mut rooks = ExactCover::new(4, 2);
+        for row in 0..2 {
+            for col in 0..2 {
+                let colour = 4 + (row + col) % 2;
+                rooks.add_row(&[row, 2 + col, colour]);
+            }
+        }
+        assert_eq!(rooks.count_solutions(None), 0);
+
+        // a secondary column may also be left uncovered
+        let mut optional = ExactCover::new(1, 1);
+        optional.add_row(&[0]);
+        optional.add_row(&[0, 1]);
+        assert_eq!(optional.count_solutions(None), 2);
+    }
+}
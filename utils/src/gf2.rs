@@ -0,0 +1,364 @@
+use thiserror::Error;
+
+/// The most null space vectors min_weight_solution will combine
+const MAX_COSET_BITS: usize = 24;
+
+/// The biggest rank min_weight_solution will search right hand sides
+/// for. That search keeps 4 bytes per right hand side, so 4 MB here
+const MAX_SYNDROME_BITS: usize = 20;
+
+/// Marks a right hand side the syndrome search hasn't reached yet
+const UNREACHED: u32 = u32::MAX;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum Gf2Error {
+    #[error("the system has no solution")]
+    NoSolution,
+    #[error("nullity {nullity} and rank {rank} are both too big to search")]
+    SearchTooLarge { nullity: usize, rank: usize },
+}
+
+/// A matrix over GF(2), the field with just 0 and 1 where addition is
+/// XOR. Each row is packed into u64 words so row operations are cheap
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gf2Matrix {
+    pub n_rows: usize,
+    pub n_cols: usize,
+    words_per_row: usize,
+    words: Vec<u64>,
+}
+
+impl Gf2Matrix {
+    pub fn zeros(n_rows: usize, n_cols: usize) -> Self {
+        let words_per_row = n_cols.div_ceil(64);
+        Self {
+            n_rows,
+            n_cols,
+            words_per_row,
+            words: vec![0; n_rows * words_per_row],
+        }
+    }
+
+    pub fn from_rows(rows: &[Vec<bool>]) -> Self {
+        let n_cols = rows.first().map_or(0, |row| row.len());
+        let mut result = Self::zeros(rows.len(), n_cols);
+        for (row_idx, row) in rows.iter().enumerate() {
+            for (col_idx, val) in row.iter().enumerate() {
+                result.set(row_idx, col_idx, *val);
+            }
+        }
+        result
+    }
+
+    fn word_and_mask(&self, row: usize, col: usize) -> (usize, u64) {
+        if row >= self.n_rows || col >= self.n_cols {
+            panic!(
+                "({}, {}) is outside a {}x{} matrix",
+                row, col, self.n_rows, self.n_cols
+            );
+        }
+        (row * self.words_per_row + col / 64, 1 << (col % 64))
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> bool {
+        let (word, mask) = self.word_and_mask(row, col);
+        self.words[word] & mask != 0
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, val: bool) {
+        let (word, mask) = self.word_and_mask(row, col);
+        if val {
+            self.words[word] |= mask;
+        } else {
+            self.words[word] &= !mask;
+        }
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        for offset in 0..self.words_per_row {
+            self.words.swap(
+                a * self.words_per_row + offset,
+                b * self.words_per_row + offset,
+            );
+        }
+    }
+
+    /// row target += row source
+    fn add_row(&mut self, target: usize, source: usize) {
+        for offset in 0..self.words_per_row {
+            self.words[target * self.words_per_row + offset] ^=
+                self.words[source * self.words_per_row + offset];
+        }
+    }
+
+    /// Gauss-Jordan elimination in place, leaving the matrix in reduced
+    /// row echelon form. Returns the pivot column of each nonzero row
+    pub fn eliminate(&mut self) -> Vec<usize> {
+        let mut pivots: Vec<usize> = vec![];
+        for col in 0..self.n_cols {
+            let n_pivots = pivots.len();
+            let pivot_row = match (n_pivots..self.n_rows).find(|row| self.get(*row, col)) {
+                Some(pivot_row) => pivot_row,
+                None => continue,
+            };
+            self.swap_rows(n_pivots, pivot_row);
+            for row in 0..self.n_rows {
+                if row != n_pivots && self.get(row, col) {
+                    self.add_row(row, n_pivots);
+                }
+            }
+            pivots.push(col);
+        }
+        pivots
+    }
+
+    pub fn rank(&self) -> usize {
+        self.clone().eliminate().len()
+    }
+
+    /// A basis for every x with self * x = 0
+    pub fn null_space(&self) -> Vec<Vec<bool>> {
+        let mut reduced = self.clone();
+        let pivots = reduced.eliminate();
+
+        (0..self.n_cols)
+            .filter(|col| !pivots.contains(col))
+            .map(|free| {
+                let mut vector = vec![false; self.n_cols];
+                vector[free] = true;
+                for (row, pivot) in pivots.iter().enumerate() {
+                    vector[*pivot] = reduced.get(row, free);
+                }
+                vector
+            })
+            .collect()
+    }
+
+    /// Some x with self * x = rhs, if there is one (free variables are 0)
+    pub fn solve(&self, rhs: &[bool]) -> Option<Vec<bool>> {
+        if rhs.len() != self.n_rows {
+            panic!("rhs has {} entries, expected {}", rhs.len(), self.n_rows);
+        }
+
+        let mut augmented = Self::zeros(self.n_rows, self.n_cols + 1);
+        for (row, rhs_bit) in rhs.iter().enumerate() {
+            for col in 0..self.n_cols {
+                augmented.set(row, col, self.get(row, col));
+            }
+            augmented.set(row, self.n_cols, *rhs_bit);
+        }
+
+        let pivots = augmented.eliminate();
+        if pivots.last() == Some(&self.n_cols) {
+            // a row reading 0 = 1
+            return None;
+        }
+
+        let mut result = vec![false; self.n_cols];
+        for (row, pivot) in pivots.iter().enumerate() {
+            result[*pivot] = augmented.get(row, self.n_cols);
+        }
+        Some(result)
+    }
+
+    /// The solution of self * x = rhs with the fewest 1s. That's a
+    /// search over either every combination of null space vectors or
+    /// every reachable right hand side, whichever is smaller, so it's
+    /// exponential in min(nullity, rank). Errors if there's no
+    /// solution, or if nullity is more than 24 and rank more than 20
+    pub fn min_weight_solution(&self, rhs: &[bool]) -> Result<Vec<bool>, Gf2Error> {
+        let current = self.solve(rhs).ok_or(Gf2Error::NoSolution)?;
+        let null_space = self.null_space();
+        let nullity = null_space.len();
+        let rank = self.n_cols - nullity;
+
+        if nullity <= MAX_COSET_BITS && (nullity <= rank || rank > MAX_SYNDROME_BITS) {
+            Ok(Self::min_weight_in_coset(current, &null_space))
+        } else if rank <= MAX_SYNDROME_BITS {
+            self.min_weight_by_syndrome(rhs).ok_or(Gf2Error::NoSolution)
+        } else {
+            Err(Gf2Error::SearchTooLarge { nullity, rank })
+        }
+    }
+
+    /// Walk the combinations of null space vectors in Gray code order,
+    /// so each step adds exactly one basis vector to current
+    fn min_weight_in_coset(mut current: Vec<bool>, null_space: &[Vec<bool>]) -> Vec<bool> {
+        let weight = |x: &[bool]| x.iter().filter(|bit| **bit).count();
+        let mut best = current.clone();
+        for step in 1u64..(1 << null_space.len()) {
+            let flipped = step.trailing_zeros() as usize;
+            for (bit, basis_bit) in current.iter_mut().zip(null_space[flipped].iter()) {
+                *bit ^= *basis_bit;
+            }
+            if weight(&current) < weight(&best) {
+                best = current.clone();
+            }
+        }
+        best
+    }
+
+    /// Breadth first search over right hand sides: after elimination
+    /// there are only 2^rank of them, each column is an edge, and the
+    /// shortest path to rhs never needs a column twice
+    fn min_weight_by_syndrome(&self, rhs: &[bool]) -> Option<Vec<bool>> {
+        let mut augmented = Self::zeros(self.n_rows, self.n_cols + 1);
+        for (row, rhs_bit) in rhs.iter().enumerate() {
+            for col in 0..self.n_cols {
+                augmented.set(row, col, self.get(row, col));
+            }
+            augmented.set(row, self.n_cols, *rhs_bit);
+        }
+        let rank = augmented.eliminate().len();
+        let syndrome = |col: usize| {
+            (0..rank).fold(0usize, |acc, row| {
+                acc | usize::from(augmented.get(row, col)) << row
+            })
+        };
+        let columns: Vec<usize> = (0..self.n_cols).map(syndrome).collect();
+        let target = syndrome(self.n_cols);
+
+        // the column each syndrome was first reached by; the previous
+        // syndrome is this one XOR that column's
+        let mut reached: Vec<u32> = vec![UNREACHED; 1 << rank];
+        let mut queue = std::collections::VecDeque::from([0usize]);
+        while let Some(state) = queue.pop_front() {
+            if state == target {
+                break;
+            }
+            for (col, column) in columns.iter().enumerate() {
+                let next = state ^ column;
+                if next != 0 && reached[next] == UNREACHED {
+                    reached[next] = u32::try_from(col).expect("too many columns");
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        let mut result = vec![false; self.n_cols];
+        let mut state = target;
+        while state != 0 {
+            let col = match reached[state] {
+                UNREACHED => return None,
+                col => col as usize,
+            };
+            result[col] = true;
+            state ^= columns[col];
+        }
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matrix(rows: &[&str]) -> Gf2Matrix {
+        Gf2Matrix::from_rows(
+            &rows
+                .iter()
+                .map(|row| row.chars().map(|c| c == '1').collect())
+                .collect::<Vec<Vec<bool>>>(),
+        )
+    }
+
+    fn times(m: &Gf2Matrix, x: &[bool]) -> Vec<bool> {
+        (0..m.n_rows)
+            .map(|row| {
+                (0..m.n_cols)
+                    .filter(|col| m.get(row, *col) && x[*col])
+                    .count()
+                    % 2
+                    == 1
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rank_and_null_space() {
+        let m = matrix(&["1100", "0110", "1010"]);
+        assert_eq!(m.rank(), 2);
+        let null_space = m.null_space();
+        assert_eq!(null_space.len(), 2);
+        for vector in null_space.iter() {
+            assert_eq!(times(&m, vector), vec![false; 3]);
+        }
+
+        // wide enough to need more than one word per row
+        let mut wide = Gf2Matrix::zeros(2, 130);
+        wide.set(0, 129, true);
+        wide.set(1, 3, true);
+        wide.set(1, 129, true);
+        assert_eq!(wide.rank(), 2);
+        assert_eq!(wide.null_space().len(), 128);
+    }
+
+    #[test]
+    fn test_solve() {
+        // day 10's first example: lights are rows, buttons are columns
+        let m = matrix(&["000011", "010001", "001110", "110100"]);
+        let rhs = [false, true, true, false];
+        let x = m.solve(&rhs).unwrap();
+        assert_eq!(times(&m, &x), rhs.to_vec());
+
+        let best = m.min_weight_solution(&rhs).unwrap();
+        assert_eq!(times(&m, &best), rhs.to_vec());
+        assert_eq!(best.iter().filter(|bit| **bit).count(), 2);
+
+        assert_eq!(matrix(&["11", "11"]).solve(&[true, false]), None);
+    }
+
+    #[test]
+    fn test_min_weight_solution_wide() {
+        // nullity 128, so this has to search over the rank instead
+        let mut wide = Gf2Matrix::zeros(2, 130);
+        wide.set(0, 129, true);
+        wide.set(1, 3, true);
+        wide.set(1, 129, true);
+        wide.set(1, 64, true);
+        wide.set(1, 100, true);
+        let rhs = [true, false];
+        let best = wide.min_weight_solution(&rhs).unwrap();
+        assert_eq!(times(&wide, &best), rhs.to_vec());
+        assert_eq!(best.iter().filter(|bit| **bit).count(), 2);
+        assert_eq!(
+            wide.min_weight_solution(&[false, false]),
+            Ok(vec![false; 130])
+        );
+        assert_eq!(
+            matrix(&["11", "11"]).min_weight_solution(&[true, false]),
+            Err(Gf2Error::NoSolution)
+        );
+
+        // 30 independent rows padded with 30 duplicate columns
+        let mut huge = Gf2Matrix::zeros(30, 60);
+        for idx in 0..30 {
+            huge.set(idx, idx, true);
+            huge.set(idx, idx + 30, true);
+        }
+        assert_eq!(
+            huge.min_weight_solution(&[true; 30]),
+            Err(Gf2Error::SearchTooLarge {
+                nullity: 30,
+                rank: 30
+            })
+        );
+
+        // both searches agree on a matrix where either would do
+        let m = matrix(&[
+            "1011001110100",
+            "0110110011010",
+            "1100011101001",
+            "0011101010111",
+            "1110000111011",
+        ]);
+        let rhs = [true, false, true, true, false];
+        let by_coset = Gf2Matrix::min_weight_in_coset(m.solve(&rhs).unwrap(), &m.null_space());
+        let by_syndrome = m.min_weight_by_syndrome(&rhs).unwrap();
+        assert_eq!(times(&m, &by_syndrome), rhs.to_vec());
+        assert_eq!(
+            by_coset.iter().filter(|bit| **bit).count(),
+            by_syndrome.iter().filter(|bit| **bit).count()
+        );
+    }
+}
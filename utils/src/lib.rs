@@ -9,6 +9,40 @@ use std::{
 
 use num::Integer;
 
+mod bit_state;
+mod components;
+mod compress;
+mod digit_dp;
+mod digit_patterns;
+mod dlx;
+mod gf2;
+mod linear_program;
+mod modular_counter;
+mod monotonic;
+mod number_theory;
+mod union_find;
+
+pub use bit_state::{BitState, ParseBitStateError};
+pub use components::{abs_diff, bounded_step, split_components, ComponentsError};
+pub use compress::CoordinateCompressor;
+pub use digit_dp::{DigitAutomaton, DigitDp};
+pub use digit_patterns::{
+    count_periodic, count_repeated_blocks, n_digits, sum_periodic, sum_repeated_blocks,
+};
+pub use dlx::{ExactCover, SearchStats};
+pub use gf2::{Gf2Error, Gf2Matrix};
+pub use linear_program::{LinearProgram, Outcome, Relation, Solution};
+pub use modular_counter::{ModularCounter, Move};
+pub use monotonic::{
+    largest_subsequence, sliding_window_max, sliding_window_min, smallest_subsequence,
+    try_index_of_max, try_index_of_min, Tie,
+};
+pub use number_theory::{
+    crt, divisors, extended_gcd, factorize, gcd_all, is_prime, lcm_all, mod_inverse, mod_pow,
+    prime_sieve, CrtError,
+};
+pub use union_find::UnionFind;
+
 fn open_file(file_path: &str) -> File {
     File::open(file_path).unwrap()
 }
@@ -201,43 +235,21 @@ fn backpropagate<N: Eq + PartialEq + Hash + Clone + Debug>(
     }
 }
 
+/// Every positive divisor of n, ascending
 pub fn factors(n: usize) -> Vec<usize> {
-    let mut result: HashSet<usize> = HashSet::new();
-    let mut candidate = 1usize;
-    loop {
-        if candidate * candidate > n {
-            break;
-        }
-
-        if n % candidate == 0 {
-            result.insert(candidate);
-            result.insert(n / candidate);
-        }
-
-        candidate += 1;
-    }
-    let mut result_vec: Vec<usize> = result.into_iter().collect();
-    result_vec.sort();
-    result_vec
+    divisors(n as u64)
+        .into_iter()
+        .map(|divisor| divisor as usize)
+        .collect()
 }
 
 /// Return the index of the largest element in non-empty Vec<T>
-/// alongside the largest element
+/// alongside the largest element. Ties go to the first one
 pub fn index_of_max<T: Ord + Debug>(slice: &[T]) -> (usize, &T) {
-    if slice.is_empty() {
-        panic!("called index_of_max on an empty vec")
-    }
-
-    let mut max_val = &slice[0];
-    let mut max_val_idx = 0usize;
-
-    for (idx, val) in slice.iter().enumerate() {
-        if val > max_val {
-            max_val = val;
-            max_val_idx = idx;
-        }
+    match try_index_of_max(slice, Tie::First) {
+        Some(result) => result,
+        None => panic!("called index_of_max on an empty vec"),
     }
-    (max_val_idx, max_val)
 }
 
 pub fn pop_set<T: Clone + Eq + Hash>(set: &mut HashSet<T>) -> Option<T> {
@@ -270,6 +282,6 @@ mod tests {
 
     #[test]
     fn test_index_of_max() {
-        assert_eq!(index_of_max(&vec![0, 1, 3, 2]), (2, &3))
+        assert_eq!(index_of_max(&[0, 1, 3, 2]), (2, &3))
     }
 }
@@ -0,0 +1,476 @@
+use num::rational::BigRational;
+use num::{BigInt, Integer, One, Signed, ToPrimitive, Zero};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relation {
+    LessEqual,
+    Equal,
+    GreaterEqual,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution<T> {
+    pub objective: T,
+    pub x: Vec<T>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome<T> {
+    Optimal(Solution<T>),
+    Infeasible,
+    Unbounded,
+    /// Only from solve_integer: the relaxation is unbounded, and the
+    /// search gave up before finding out whether there's any integer
+    /// point at all
+    Undetermined,
+}
+
+impl<T> Outcome<T> {
+    pub fn optimal(self) -> Option<Solution<T>> {
+        match self {
+            Outcome::Optimal(solution) => Some(solution),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Constraint {
+    coeffs: Vec<BigRational>,
+    relation: Relation,
+    rhs: BigRational,
+}
+
+/// Minimize objective . x subject to linear constraints and x >= 0.
+/// All arithmetic is exact, so there's no epsilon fiddling, and
+/// branch-and-bound can tell for sure whether a value is an integer
+#[derive(Debug, Clone)]
+pub struct LinearProgram {
+    objective: Vec<BigRational>,
+    constraints: Vec<Constraint>,
+}
+
+/// How many branches solve_integer will try when looking for any
+/// integer point at all under an unbounded relaxation
+const MAX_FEASIBILITY_NODES: usize = 20;
+
+fn rational(n: i64) -> BigRational {
+    BigRational::from_integer(BigInt::from(n))
+}
+
+impl LinearProgram {
+    /// One variable per objective coefficient
+    pub fn minimize(objective: &[i64]) -> Self {
+        Self {
+            objective: objective.iter().map(|c| rational(*c)).collect(),
+            constraints: vec![],
+        }
+    }
+
+    pub fn n_vars(&self) -> usize {
+        self.objective.len()
+    }
+
+    pub fn add_constraint(&mut self, coeffs: &[i64], relation: Relation, rhs: i64) {
+        if coeffs.len() != self.n_vars() {
+            panic!(
+                "constraint has {} coefficients, expected {}",
+                coeffs.len(),
+                self.n_vars()
+            );
+        }
+        self.constraints.push(Constraint {
+            coeffs: coeffs.iter().map(|c| rational(*c)).collect(),
+            relation,
+            rhs: rational(rhs),
+        });
+    }
+
+    fn with_bound(&self, var: usize, relation: Relation, bound: BigRational) -> Self {
+        let mut result = self.clone();
+        let mut coeffs = vec![BigRational::zero(); self.n_vars()];
+        coeffs[var] = BigRational::one();
+        result.constraints.push(Constraint {
+            coeffs,
+            relation,
+            rhs: bound,
+        });
+        result
+    }
+
+    /// Solve the relaxation (x real) with the two-phase simplex method
+    pub fn solve(&self) -> Outcome<BigRational> {
+        let mut tableau = Tableau::new(self);
+
+        // phase 1: drive the artificial variables to zero
+        let phase_1_cost: Vec<BigRational> = (0..tableau.n_cols)
+            .map(|col| {
+                if col >= tableau.first_artificial {
+                    BigRational::one()
+                } else {
+                    BigRational::zero()
+                }
+            })
+            .collect();
+        tableau.optimize(&phase_1_cost, tableau.n_cols);
+        if !tableau.objective_value(&phase_1_cost).is_zero() {
+            return Outcome::Infeasible;
+        }
+        tableau.evict_artificials();
+
+        // phase 2: the real objective, never letting artificials back in
+        let mut cost = self.objective.clone();
+        cost.resize(tableau.n_cols, BigRational::zero());
+        if !tableau.optimize(&cost, tableau.first_artificial) {
+            return Outcome::Unbounded;
+        }
+
+        let mut x = vec![BigRational::zero(); self.n_vars()];
+        for (row, var) in tableau.basis.iter().enumerate() {
+            if *var < self.n_vars() {
+                x[*var] = tableau.rhs(row).clone();
+            }
+        }
+        Outcome::Optimal(Solution {
+            objective: tableau.objective_value(&cost),
+            x,
+        })
+    }
+
+    /// Solve with x restricted to integers, by branch-and-bound on the
+    /// relaxation. The objective's coefficients are integers, so a
+    /// branch whose relaxation rounds up to no better than the best so
+    /// far can't help. Panics if any value doesn't fit in an i64
+    ///
+    /// An unbounded relaxation doesn't settle it: 2x - 2y = 1 has no
+    /// integer points at all (which the gcd of its coefficients gives
+    /// away, but that won't catch everything). But the constraints are rational, so as
+    /// soon as there's one integer point there are integer points
+    /// arbitrarily far along the unbounded direction too. So we look
+    /// for any integer point, and give up as Undetermined if that
+    /// takes too long
+    pub fn solve_integer(&self) -> Outcome<i64> {
+        if !self.equalities_divisible() {
+            return Outcome::Infeasible;
+        }
+        match self.solve() {
+            Outcome::Unbounded => {
+                let mut feasibility = self.clone();
+                feasibility.objective = vec![BigRational::zero(); self.n_vars()];
+                match feasibility.branch_and_bound(MAX_FEASIBILITY_NODES) {
+                    Outcome::Optimal(_) => Outcome::Unbounded,
+                    outcome => outcome,
+                }
+            }
+            _ => self.branch_and_bound(usize::MAX),
+        }
+    }
+
+    /// Whether every equality's rhs is a multiple of the gcd of its
+    /// coefficients, which it has to be for any integer x to satisfy it
+    fn equalities_divisible(&self) -> bool {
+        self.constraints
+            .iter()
+            .filter(|constraint| constraint.relation == Relation::Equal)
+            .all(|constraint| {
+                let gcd = constraint
+                    .coeffs
+                    .iter()
+                    .fold(BigInt::zero(), |acc, c| acc.gcd(&c.to_integer()));
+                gcd.is_zero() || (constraint.rhs.to_integer() % gcd).is_zero()
+            })
+    }
+
+    /// solve_integer on a program whose relaxation is bounded (so every
+    /// branch's is too), visiting at most max_nodes branches
+    fn branch_and_bound(&self, max_nodes: usize) -> Outcome<i64> {
+        let mut best: Option<Solution<BigRational>> = None;
+        let mut to_visit: Vec<Self> = vec![self.clone()];
+
+        for _ in 0..max_nodes {
+            let program = match to_visit.pop() {
+                Some(program) => program,
+                None => break,
+            };
+            let relaxed = match program.solve() {
+                Outcome::Optimal(relaxed) => relaxed,
+                Outcome::Infeasible => continue,
+                _ => unreachable!("branches of a bounded program are bounded"),
+            };
+            if best
+                .as_ref()
+                .is_some_and(|best| relaxed.objective.ceil() >= best.objective)
+            {
+                continue;
+            }
+
+            match relaxed.x.iter().position(|value| !value.is_integer()) {
+                None => best = Some(relaxed),
+                Some(var) => {
+                    // visit the side the relaxation leans towards first,
+                    // so we find a good bound sooner
+                    let value = &relaxed.x[var];
+                    let round_up = value.fract() >= BigRational::new(1.into(), 2.into());
+                    let down = program.with_bound(var, Relation::LessEqual, value.floor());
+                    let up = program.with_bound(var, Relation::GreaterEqual, value.ceil());
+                    if round_up {
+                        to_visit.extend([down, up]);
+                    } else {
+                        to_visit.extend([up, down]);
+                    }
+                }
+            }
+        }
+        if !to_visit.is_empty() {
+            return Outcome::Undetermined;
+        }
+
+        match best {
+            Some(best) => {
+                let to_i64 = |value: &BigRational| value.to_integer().to_i64().unwrap();
+                Outcome::Optimal(Solution {
+                    objective: to_i64(&best.objective),
+                    x: best.x.iter().map(to_i64).collect(),
+                })
+            }
+            None => Outcome::Infeasible,
+        }
+    }
+}
+
+/// Rows are constraints in equality form, columns are the program's
+/// variables, then slack/surplus variables, then artificials; the
+/// final column holds the right-hand sides
+struct Tableau {
+    rows: Vec<Vec<BigRational>>,
+    basis: Vec<usize>,
+    n_cols: usize,
+    first_artificial: usize,
+}
+
+impl Tableau {
+    fn new(program: &LinearProgram) -> Self {
+        let n_vars = program.n_vars();
+        // flip rows so every rhs is nonnegative
+        let constraints: Vec<Constraint> = program
+            .constraints
+            .iter()
+            .map(|constraint| {
+                if constraint.rhs.is_negative() {
+                    Constraint {
+                        coeffs: constraint.coeffs.iter().map(|c| -c).collect(),
+                        relation: match constraint.relation {
+                            Relation::LessEqual => Relation::GreaterEqual,
+                            Relation::Equal => Relation::Equal,
+                            Relation::GreaterEqual => Relation::LessEqual,
+                        },
+                        rhs: -&constraint.rhs,
+                    }
+                } else {
+                    constraint.clone()
+                }
+            })
+            .collect();
+
+        let n_slack = constraints
+            .iter()
+            .filter(|c| c.relation != Relation::Equal)
+            .count();
+        let first_artificial = n_vars + n_slack;
+        let n_cols = first_artificial + constraints.len();
+
+        let mut rows: Vec<Vec<BigRational>> = vec![];
+        let mut basis: Vec<usize> = vec![];
+        let mut slack = n_vars;
+        for (row_idx, constraint) in constraints.into_iter().enumerate() {
+            let mut row = constraint.coeffs;
+            row.resize(n_cols + 1, BigRational::zero());
+            row[n_cols] = constraint.rhs;
+            match constraint.relation {
+                Relation::LessEqual => {
+                    row[slack] = BigRational::one();
+                    basis.push(slack);
+                    slack += 1;
+                }
+                Relation::GreaterEqual => {
+                    row[slack] = -BigRational::one();
+                    slack += 1;
+                    row[first_artificial + row_idx] = BigRational::one();
+                    basis.push(first_artificial + row_idx);
+                }
+                Relation::Equal => {
+                    row[first_artificial + row_idx] = BigRational::one();
+                    basis.push(first_artificial + row_idx);
+                }
+            }
+            rows.push(row);
+        }
+
+        Self {
+            rows,
+            basis,
+            n_cols,
+            first_artificial,
+        }
+    }
+
+    fn rhs(&self, row: usize) -> &BigRational {
+        &self.rows[row][self.n_cols]
+    }
+
+    fn objective_value(&self, cost: &[BigRational]) -> BigRational {
+        self.basis
+            .iter()
+            .enumerate()
+            .map(|(row, var)| &cost[*var] * self.rhs(row))
+            .sum()
+    }
+
+    fn pivot(&mut self, pivot_row: usize, pivot_col: usize) {
+        let factor = self.rows[pivot_row][pivot_col].clone();
+        for val in self.rows[pivot_row].iter_mut() {
+            *val /= &factor;
+        }
+        let pivot = self.rows[pivot_row].clone();
+        for (row_idx, row) in self.rows.iter_mut().enumerate() {
+            if row_idx == pivot_row || row[pivot_col].is_zero() {
+                continue;
+            }
+            let multiple = row[pivot_col].clone();
+            for (val, pivot_val) in row.iter_mut().zip(pivot.iter()) {
+                *val -= &multiple * pivot_val;
+            }
+        }
+        self.basis[pivot_row] = pivot_col;
+    }
+
+    /// Minimize cost, only letting columns below n_enterable into the
+    /// basis. Uses Bland's rule so it can't cycle. Returns false if
+    /// the objective is unbounded
+    fn optimize(&mut self, cost: &[BigRational], n_enterable: usize) -> bool {
+        loop {
+            let entering = (0..n_enterable).find(|col| {
+                let reduced: BigRational = &cost[*col]
+                    - self
+                        .basis
+                        .iter()
+                        .enumerate()
+                        .map(|(row, var)| &cost[*var] * &self.rows[row][*col])
+                        .sum::<BigRational>();
+                reduced.is_negative()
+            });
+            let entering = match entering {
+                Some(entering) => entering,
+                None => return true,
+            };
+
+            let leaving = (0..self.rows.len())
+                .filter(|row| self.rows[*row][entering].is_positive())
+                .min_by(|a, b| {
+                    let ratio = |row: usize| self.rhs(row) / &self.rows[row][entering];
+                    ratio(*a)
+                        .cmp(&ratio(*b))
+                        .then(self.basis[*a].cmp(&self.basis[*b]))
+                });
+            match leaving {
+                Some(leaving) => self.pivot(leaving, entering),
+                None => return false,
+            }
+        }
+    }
+
+    /// After phase 1, any artificial still in the basis is at zero.
+    /// Swap it for a real column if its row has one, otherwise the row
+    /// was redundant and can go
+    fn evict_artificials(&mut self) {
+        let mut row = 0;
+        while row < self.rows.len() {
+            if self.basis[row] < self.first_artificial {
+                row += 1;
+                continue;
+            }
+            match (0..self.first_artificial).find(|col| !self.rows[row][*col].is_zero()) {
+                Some(col) => {
+                    self.pivot(row, col);
+                    row += 1;
+                }
+                None => {
+                    self.rows.remove(row);
+                    self.basis.remove(row);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relaxation() {
+        // maximize x + y (minimize -x - y) with x + 2y <= 4, 3x + y <= 6
+        let mut program = LinearProgram::minimize(&[-1, -1]);
+        program.add_constraint(&[1, 2], Relation::LessEqual, 4);
+        program.add_constraint(&[3, 1], Relation::LessEqual, 6);
+        let solution = program.solve().optimal().unwrap();
+        assert_eq!(solution.objective, BigRational::new((-14).into(), 5.into()));
+        assert_eq!(solution.x[0], BigRational::new(8.into(), 5.into()));
+
+        let integer = program.solve_integer().optimal().unwrap();
+        assert_eq!(integer.objective, -2);
+
+        let mut infeasible = LinearProgram::minimize(&[1]);
+        infeasible.add_constraint(&[1], Relation::GreaterEqual, 3);
+        infeasible.add_constraint(&[1], Relation::LessEqual, 2);
+        assert_eq!(infeasible.solve(), Outcome::Infeasible);
+
+        let mut unbounded = LinearProgram::minimize(&[-1, 0]);
+        unbounded.add_constraint(&[1, -1], Relation::Equal, 1);
+        assert_eq!(unbounded.solve(), Outcome::Unbounded);
+        assert_eq!(unbounded.solve_integer(), Outcome::Unbounded);
+
+        // integer points exist, and x can grow forever among them
+        let mut split = LinearProgram::minimize(&[-1, 0]);
+        split.add_constraint(&[2, -1], Relation::GreaterEqual, 1);
+        assert_eq!(split.solve_integer(), Outcome::Unbounded);
+
+        // the relaxation is unbounded but there are no integer points
+        let mut no_integers = LinearProgram::minimize(&[-1, 0]);
+        no_integers.add_constraint(&[2, -2], Relation::Equal, 1);
+        assert_eq!(no_integers.solve(), Outcome::Unbounded);
+        assert_eq!(no_integers.solve_integer(), Outcome::Infeasible);
+
+        // x + y is both odd and even, which no single row gives away,
+        // and branching never runs out, so all we can say is we don't know
+        let mut parity = LinearProgram::minimize(&[-1, 0, 0, 0]);
+        parity.add_constraint(&[1, 1, -2, 0], Relation::Equal, 1);
+        parity.add_constraint(&[1, 1, 0, -2], Relation::Equal, 0);
+        assert_eq!(parity.solve(), Outcome::Unbounded);
+        assert_eq!(parity.solve_integer(), Outcome::Undetermined);
+    }
+
+    #[test]
+    fn test_integer_equalities() {
+        // day 10's first machine: fewest presses reaching {3,5,4,7}
+        let buttons: [&[usize]; 6] = [&[3], &[1, 3], &[2], &[2, 3], &[0, 2], &[0, 1]];
+        let target = [3, 5, 4, 7];
+        let mut program = LinearProgram::minimize(&[1; 6]);
+        for (counter, goal) in target.iter().enumerate() {
+            let coeffs: Vec<i64> = buttons
+                .iter()
+                .map(|button| i64::from(button.contains(&counter)))
+                .collect();
+            program.add_constraint(&coeffs, Relation::Equal, *goal);
+        }
+        let solution = program.solve_integer().optimal().unwrap();
+        assert_eq!(solution.objective, 10);
+        assert_eq!(solution.x.iter().sum::<i64>(), 10);
+
+        // 2x = 3 has a real solution but no integer one
+        let mut odd = LinearProgram::minimize(&[1]);
+        odd.add_constraint(&[2], Relation::Equal, 3);
+        assert!(odd.solve().optimal().is_some());
+        assert_eq!(odd.solve_integer(), Outcome::Infeasible);
+    }
+}
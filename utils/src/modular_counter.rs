@@ -0,0 +1,143 @@
+use direction::Rotation;
+use num::{Integer, Signed};
+
+/// Where a move ended up, and how many times it passed or landed on a mark
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move<T> {
+    pub position: T,
+    pub passes: T,
+}
+
+/// A position on a dial numbered 0..modulus that wraps around, with
+/// some positions marked so we can count how often moves reach them.
+/// Clockwise counts up
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModularCounter<T> {
+    modulus: T,
+    position: T,
+    marks: Vec<T>,
+}
+
+impl<T: Integer + Signed + Copy> ModularCounter<T> {
+    pub fn new(modulus: T, position: T) -> Self {
+        if modulus <= T::zero() {
+            panic!("modulus must be positive");
+        }
+        ModularCounter {
+            modulus,
+            position: position.mod_floor(&modulus),
+            marks: vec![],
+        }
+    }
+
+    /// Marks that land on the same position only count once
+    pub fn with_mark(mut self, mark: T) -> Self {
+        let mark = mark.mod_floor(&self.modulus);
+        if !self.marks.contains(&mark) {
+            self.marks.push(mark);
+        }
+        self
+    }
+
+    pub fn modulus(&self) -> T {
+        self.modulus
+    }
+
+    pub fn position(&self) -> T {
+        self.position
+    }
+
+    pub fn marks(&self) -> &[T] {
+        &self.marks
+    }
+
+    /// How many of the clicks in a move of delta from `from` land on
+    /// mark. The starting position doesn't count, so a move that ends
+    /// on a mark and the next one that leaves it only count it once
+    fn passes_of(&self, from: T, delta: T, mark: T) -> T {
+        let clicks_to_mark = if delta.is_negative() {
+            (from - mark).mod_floor(&self.modulus)
+        } else {
+            (mark - from).mod_floor(&self.modulus)
+        };
+        let clicks_to_mark = if clicks_to_mark.is_zero() {
+            self.modulus
+        } else {
+            clicks_to_mark
+        };
+
+        if delta.abs() < clicks_to_mark {
+            T::zero()
+        } else {
+            (delta.abs() - clicks_to_mark) / self.modulus + T::one()
+        }
+    }
+
+    /// Move by delta clicks, clockwise if it's positive. delta can be
+    /// anything but T's minimum, whose abs overflows, and the modulus
+    /// must be at most half of T's maximum
+    pub fn advance(&mut self, delta: T) -> Move<T> {
+        let from = self.position;
+        let passes = self.marks.iter().fold(T::zero(), |acc, mark| {
+            acc + self.passes_of(from, delta, *mark)
+        });
+        self.position = (from + delta.mod_floor(&self.modulus)).mod_floor(&self.modulus);
+        Move {
+            position: self.position,
+            passes,
+        }
+    }
+
+    /// Move n clicks in rotation's direction
+    pub fn turn(&mut self, rotation: Rotation, n: T) -> Move<T> {
+        match rotation {
+            Rotation::Clockwise => self.advance(n),
+            Rotation::CounterClockwise => self.advance(-n),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turn() {
+        let mut dial = ModularCounter::new(100, 5).with_mark(0);
+        assert_eq!(
+            dial.turn(Rotation::CounterClockwise, 10),
+            Move {
+                position: 95,
+                passes: 1
+            }
+        );
+        assert_eq!(dial.advance(5).passes, 1);
+        assert_eq!(dial.advance(-100).passes, 1);
+        assert_eq!(dial.advance(0).passes, 0);
+        assert_eq!(dial.position(), 0);
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        for start in -3..10i64 {
+            for delta in -25..25i64 {
+                let mut dial = ModularCounter::new(7, start).with_mark(2).with_mark(-1);
+                let from = dial.position();
+                let step = delta.signum();
+                let brute = (1..=delta.abs())
+                    .map(|click| (from + step * click).mod_floor(&7))
+                    .filter(|position| *position == 2 || *position == 6)
+                    .count() as i64;
+                assert_eq!(dial.advance(delta).passes, brute);
+            }
+        }
+
+        let mut dial = ModularCounter::new(100i128, 50).with_mark(0);
+        assert_eq!(dial.advance(10i128.pow(30)).passes, 10i128.pow(28));
+
+        let mut dial = ModularCounter::new(100i64, 50).with_mark(0).with_mark(100);
+        assert_eq!(dial.marks(), &[0]);
+        assert_eq!(dial.advance(i64::MAX).passes, i64::MAX / 100);
+        assert_eq!(dial.position(), 57);
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::VecDeque;
+
+/// Which element wins when several are equally good
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tie {
+    First,
+    Last,
+}
+
+fn index_of_best<T>(slice: &[T], tie: Tie, better: impl Fn(&T, &T) -> bool) -> Option<(usize, &T)> {
+    let mut result: Option<(usize, &T)> = None;
+    for (idx, val) in slice.iter().enumerate() {
+        result = match result {
+            Some((_, best)) if better(best, val) => result,
+            Some((_, best)) if tie == Tie::First && !better(val, best) => result,
+            _ => Some((idx, val)),
+        };
+    }
+    result
+}
+
+/// The index of the largest element alongside the element, or None if
+/// slice is empty
+pub fn try_index_of_max<T: Ord>(slice: &[T], tie: Tie) -> Option<(usize, &T)> {
+    index_of_best(slice, tie, |a, b| a > b)
+}
+
+/// The index of the smallest element alongside the element, or None if
+/// slice is empty
+pub fn try_index_of_min<T: Ord>(slice: &[T], tie: Tie) -> Option<(usize, &T)> {
+    index_of_best(slice, tie, |a, b| a < b)
+}
+
+/// Keep k elements of slice in order, throwing away an earlier element
+/// whenever a better one comes along and there are enough left to
+/// still fill k
+fn best_subsequence<T: Clone>(
+    slice: &[T],
+    k: usize,
+    better: impl Fn(&T, &T) -> bool,
+) -> Option<Vec<T>> {
+    if k > slice.len() {
+        return None;
+    }
+
+    let mut stack: Vec<T> = Vec::with_capacity(k);
+    for (idx, val) in slice.iter().enumerate() {
+        let n_left = slice.len() - idx;
+        while let Some(top) = stack.last() {
+            if better(val, top) && stack.len() - 1 + n_left >= k {
+                stack.pop();
+            } else {
+                break;
+            }
+        }
+        if stack.len() < k {
+            stack.push(val.clone());
+        }
+    }
+    Some(stack)
+}
+
+/// The lexicographically largest k elements of slice, kept in order.
+/// None if slice is shorter than k
+pub fn largest_subsequence<T: Ord + Clone>(slice: &[T], k: usize) -> Option<Vec<T>> {
+    best_subsequence(slice, k, |a, b| a > b)
+}
+
+/// The lexicographically smallest k elements of slice, kept in order.
+/// None if slice is shorter than k
+pub fn smallest_subsequence<T: Ord + Clone>(slice: &[T], k: usize) -> Option<Vec<T>> {
+    best_subsequence(slice, k, |a, b| a < b)
+}
+
+/// The best element of every window of width consecutive elements. The
+/// deque holds indices whose values only get worse from front to back,
+/// so the front is always the best in the current window
+fn sliding_window_best<T: Clone>(
+    slice: &[T],
+    width: usize,
+    better: impl Fn(&T, &T) -> bool,
+) -> Vec<T> {
+    if width == 0 || width > slice.len() {
+        return vec![];
+    }
+
+    let mut deque: VecDeque<usize> = VecDeque::new();
+    let mut result: Vec<T> = Vec::with_capacity(slice.len() - width + 1);
+    for (idx, val) in slice.iter().enumerate() {
+        while let Some(back) = deque.back() {
+            if better(&slice[*back], val) {
+                break;
+            }
+            deque.pop_back();
+        }
+        deque.push_back(idx);
+
+        if deque[0] + width <= idx {
+            deque.pop_front();
+        }
+        if idx + 1 >= width {
+            result.push(slice[deque[0]].clone());
+        }
+    }
+    result
+}
+
+/// The largest element of each window of width consecutive elements,
+/// in order. Empty if there are no such windows
+pub fn sliding_window_max<T: Ord + Clone>(slice: &[T], width: usize) -> Vec<T> {
+    sliding_window_best(slice, width, |a, b| a > b)
+}
+
+/// The smallest element of each window of width consecutive elements,
+/// in order. Empty if there are no such windows
+pub fn sliding_window_min<T: Ord + Clone>(slice: &[T], width: usize) -> Vec<T> {
+    sliding_window_best(slice, width, |a, b| a < b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_of_max_and_min() {
+        let values = [1, 3, 0, 3, 0];
+        assert_eq!(try_index_of_max(&values, Tie::First), Some((1, &3)));
+        assert_eq!(try_index_of_max(&values, Tie::Last), Some((3, &3)));
+        assert_eq!(try_index_of_min(&values, Tie::First), Some((2, &0)));
+        assert_eq!(try_index_of_min(&values, Tie::Last), Some((4, &0)));
+        assert_eq!(try_index_of_max::<u8>(&[], Tie::First), None);
+    }
+
+    #[test]
+    fn test_subsequences() {
+        let digits = [8, 1, 8, 1, 8, 1, 9, 1, 1, 1, 1, 2, 1, 1, 1];
+        assert_eq!(
+            largest_subsequence(&digits, 12),
+            Some(vec![8, 8, 8, 9, 1, 1, 1, 1, 2, 1, 1, 1])
+        );
+        assert_eq!(largest_subsequence(&digits, 2), Some(vec![9, 2]));
+        assert_eq!(
+            smallest_subsequence(&[3, 1, 2, 1, 3], 3),
+            Some(vec![1, 1, 3])
+        );
+        assert_eq!(largest_subsequence(&[1, 2], 3), None);
+        assert_eq!(largest_subsequence(&[1, 2], 0), Some(vec![]));
+    }
+
+    #[test]
+    fn test_sliding_windows() {
+        let values = [1, 3, -1, -3, 5, 3, 6, 7];
+        assert_eq!(sliding_window_max(&values, 3), vec![3, 3, 5, 5, 6, 7]);
+        assert_eq!(sliding_window_min(&values, 3), vec![-1, -3, -3, -3, 3, 3]);
+        assert_eq!(sliding_window_max(&values, 1), values.to_vec());
+        assert!(sliding_window_min(&values, 9).is_empty());
+    }
+}
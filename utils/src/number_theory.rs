@@ -0,0 +1,277 @@
+use num::{Integer, Signed};
+use thiserror::Error;
+
+/// Every prime <= limit, by the sieve of Eratosthenes
+pub fn prime_sieve(limit: usize) -> Vec<usize> {
+    if limit < 2 {
+        return vec![];
+    }
+
+    let mut is_prime = vec![true; limit + 1];
+    is_prime[0] = false;
+    is_prime[1] = false;
+    let mut candidate = 2;
+    while candidate * candidate <= limit {
+        if is_prime[candidate] {
+            for multiple in (candidate * candidate..=limit).step_by(candidate) {
+                is_prime[multiple] = false;
+            }
+        }
+        candidate += 1;
+    }
+
+    (0..=limit).filter(|n| is_prime[*n]).collect()
+}
+
+/// base^exp mod modulus, by repeated squaring
+pub fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    if modulus == 1 {
+        return 0;
+    }
+
+    let modulus = modulus as u128;
+    let mut base = base as u128 % modulus;
+    let mut result = 1u128;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    result as u64
+}
+
+/// Miller-Rabin with a set of witnesses known to be exact for every u64
+pub fn is_prime(n: u64) -> bool {
+    const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if n < 2 {
+        return false;
+    }
+    for p in WITNESSES {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+
+    // n - 1 = d * 2^s with d odd
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    WITNESSES.iter().all(|witness| {
+        let mut x = mod_pow(*witness, d, n);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        for _ in 1..s {
+            x = (x as u128 * x as u128 % n as u128) as u64;
+            if x == n - 1 {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+/// Some nontrivial factor of the composite n, by Pollard's rho
+fn pollard_rho(n: u64) -> u64 {
+    if n.is_multiple_of(2) {
+        return 2;
+    }
+
+    let step = |x: u64, c: u64| ((x as u128 * x as u128 + c as u128) % n as u128) as u64;
+    for c in 1.. {
+        let (mut tortoise, mut hare) = (2u64, 2u64);
+        let mut divisor = 1u64;
+        while divisor == 1 {
+            tortoise = step(tortoise, c);
+            hare = step(step(hare, c), c);
+            divisor = tortoise.abs_diff(hare).gcd(&n);
+        }
+        if divisor != n {
+            return divisor;
+        }
+    }
+    unreachable!()
+}
+
+/// The prime factorization of n as (prime, multiplicity), by ascending
+/// prime. 0 and 1 have no prime factors
+pub fn factorize(n: u64) -> Vec<(u64, u32)> {
+    let mut primes: Vec<u64> = vec![];
+    let mut to_split: Vec<u64> = if n > 1 { vec![n] } else { vec![] };
+    while let Some(m) = to_split.pop() {
+        if is_prime(m) {
+            primes.push(m);
+        } else {
+            let divisor = pollard_rho(m);
+            to_split.extend([divisor, m / divisor]);
+        }
+    }
+    primes.sort();
+
+    let mut result: Vec<(u64, u32)> = vec![];
+    for prime in primes {
+        match result.last_mut() {
+            Some((last, multiplicity)) if *last == prime => *multiplicity += 1,
+            _ => result.push((prime, 1)),
+        }
+    }
+    result
+}
+
+/// Every positive divisor of n, ascending
+pub fn divisors(n: u64) -> Vec<u64> {
+    if n == 0 {
+        return vec![];
+    }
+
+    let mut result: Vec<u64> = vec![1];
+    for (prime, multiplicity) in factorize(n) {
+        let smaller = result.clone();
+        let mut power = 1;
+        for _ in 0..multiplicity {
+            power *= prime;
+            result.extend(smaller.iter().map(|divisor| divisor * power));
+        }
+    }
+    result.sort();
+    result
+}
+
+/// The gcd of every value; 0 if there are none
+pub fn gcd_all<T: Integer + Copy>(values: impl IntoIterator<Item = T>) -> T {
+    values
+        .into_iter()
+        .fold(T::zero(), |acc, value| acc.gcd(&value))
+}
+
+/// The lcm of every value; 1 if there are none
+pub fn lcm_all<T: Integer + Copy>(values: impl IntoIterator<Item = T>) -> T {
+    values
+        .into_iter()
+        .fold(T::one(), |acc, value| acc.lcm(&value))
+}
+
+/// (g, x, y) with a * x + b * y = g = gcd(a, b)
+pub fn extended_gcd<T: Integer + Signed + Copy>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::one(), T::zero());
+    let (mut old_y, mut y) = (T::zero(), T::one());
+    while !r.is_zero() {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r.is_negative() {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// x in 0..modulus with a * x = 1 (mod modulus), if a and modulus are coprime
+pub fn mod_inverse<T: Integer + Signed + Copy>(a: T, modulus: T) -> Option<T> {
+    let (g, x, _) = extended_gcd(a.mod_floor(&modulus), modulus);
+    g.is_one().then(|| x.mod_floor(&modulus))
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum CrtError {
+    #[error("moduli must be positive, got {0}")]
+    NonPositiveModulus(i64),
+    #[error("the congruences contradict each other")]
+    Contradiction,
+    #[error("the combined modulus doesn't fit in an i128")]
+    Overflow,
+}
+
+/// Combine congruences x = residue (mod modulus) into a single
+/// (residue, modulus) pair. The moduli don't need to be coprime
+pub fn crt(congruences: &[(i64, i64)]) -> Result<(i128, i128), CrtError> {
+    let mut combined: (i128, i128) = (0, 1);
+    for (residue, modulus) in congruences.iter() {
+        if *modulus <= 0 {
+            return Err(CrtError::NonPositiveModulus(*modulus));
+        }
+        let (residue, modulus) = (*residue as i128, *modulus as i128);
+        let (g, p, _) = extended_gcd(combined.1, modulus);
+        let difference = residue - combined.0;
+        if difference % g != 0 {
+            return Err(CrtError::Contradiction);
+        }
+
+        // combined.0 + combined.1 * t = residue (mod modulus); both
+        // factors of t are reduced first so their product fits
+        let step = modulus / g;
+        let lcm = (combined.1 / g)
+            .checked_mul(modulus)
+            .ok_or(CrtError::Overflow)?;
+        let t = ((difference / g).mod_floor(&step) * p.mod_floor(&step)).mod_floor(&step);
+        combined = ((combined.0 + combined.1 * t).mod_floor(&lcm), lcm);
+    }
+    Ok(combined)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_primes() {
+        assert_eq!(prime_sieve(30), vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        let sieved = prime_sieve(10_000);
+        assert!((0..10_000u64).all(|n| is_prime(n) == sieved.contains(&(n as usize))));
+        assert!(is_prime(18_446_744_073_709_551_557));
+        // a strong pseudoprime to several small bases
+        assert!(!is_prime(3_215_031_751));
+        assert_eq!(mod_pow(4, 13, 497), 445);
+    }
+
+    #[test]
+    fn test_factorize_and_divisors() {
+        assert_eq!(factorize(360), vec![(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(factorize(1), vec![]);
+        assert_eq!(
+            factorize(600_851_475_143),
+            vec![(71, 1), (839, 1), (1471, 1), (6857, 1)]
+        );
+        assert_eq!(
+            factorize(4_294_967_291 * 4_294_967_279),
+            vec![(4_294_967_279, 1), (4_294_967_291, 1)]
+        );
+        assert_eq!(divisors(12), vec![1, 2, 3, 4, 6, 12]);
+        assert_eq!(divisors(360).len(), 24);
+    }
+
+    #[test]
+    fn test_modular_arithmetic() {
+        assert_eq!(gcd_all([12, 18, 30]), 6);
+        assert_eq!(lcm_all([4, 6, 10]), 60);
+        assert_eq!(lcm_all(Vec::<u32>::new()), 1);
+
+        let (g, x, y) = extended_gcd(240i64, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+        assert_eq!(mod_inverse(3i64, 11), Some(4));
+        assert_eq!(mod_inverse(-3i64, 11), Some(7));
+        assert_eq!(mod_inverse(6i64, 9), None);
+
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Ok((23, 105)));
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Ok((9, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), Err(CrtError::Contradiction));
+        assert_eq!(crt(&[(1, 4), (2, 0)]), Err(CrtError::NonPositiveModulus(0)));
+        assert_eq!(crt(&[(1, -5)]), Err(CrtError::NonPositiveModulus(-5)));
+
+        // bigger than an i64, but still exact
+        let big = [(1, 4_294_967_291), (2, 4_294_967_279), (3, 4_294_967_231)];
+        let (residue, modulus) = crt(&big).unwrap();
+        assert_eq!(modulus, 4_294_967_291i128 * 4_294_967_279 * 4_294_967_231);
+        for (r, m) in big {
+            assert_eq!(residue % m as i128, r as i128);
+        }
+        let huge = [(0, i64::MAX), (0, i64::MAX - 1), (0, i64::MAX - 2)];
+        assert_eq!(crt(&huge), Err(CrtError::Overflow));
+    }
+}
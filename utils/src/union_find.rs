@@ -0,0 +1,70 @@
+/// Disjoint sets over 0..n, with path halving and union by size
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+    n_sets: usize,
+}
+
+impl UnionFind {
+    /// n singleton sets
+    pub fn new(n: usize) -> Self {
+        UnionFind {
+            parents: (0..n).collect(),
+            sizes: vec![1; n],
+            n_sets: n,
+        }
+    }
+
+    /// The representative of idx's set
+    pub fn find(&mut self, mut idx: usize) -> usize {
+        while self.parents[idx] != idx {
+            self.parents[idx] = self.parents[self.parents[idx]];
+            idx = self.parents[idx];
+        }
+        idx
+    }
+
+    /// Merge the sets holding a and b. False if they were already one set
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.sizes[a] < self.sizes[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parents[b] = a;
+        self.sizes[a] += self.sizes[b];
+        self.n_sets -= 1;
+        true
+    }
+
+    pub fn size_of(&mut self, idx: usize) -> usize {
+        let root = self.find(idx);
+        self.sizes[root]
+    }
+
+    pub fn n_sets(&self) -> usize {
+        self.n_sets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union_find() {
+        let mut sets = UnionFind::new(5);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(3, 4));
+        assert!(!sets.union(1, 0));
+        assert_eq!(sets.n_sets(), 3);
+        assert_eq!(sets.size_of(4), 2);
+        assert!(sets.union(1, 4));
+        assert_eq!(sets.find(0), sets.find(3));
+        assert_eq!(sets.size_of(0), 4);
+        assert_eq!(sets.n_sets(), 2);
+    }
+}